@@ -0,0 +1,29 @@
+use skirt::sync::{Once, OnceLock};
+
+fn main() {
+    divan::main();
+}
+
+#[divan::bench]
+fn call_once_completed(bencher: divan::Bencher) {
+    let once = Once::new();
+    once.call_once(|| {});
+
+    bencher.bench(|| divan::black_box(&once).call_once(|| unreachable!()));
+}
+
+#[divan::bench]
+fn is_completed(bencher: divan::Bencher) {
+    let once = Once::new();
+    once.call_once(|| {});
+
+    bencher.bench(|| divan::black_box(&once).is_completed());
+}
+
+#[divan::bench]
+fn get_or_init_initialized(bencher: divan::Bencher) {
+    let cell = OnceLock::new();
+    cell.get_or_init(|| 42_u64);
+
+    bencher.bench(|| *divan::black_box(&cell).get_or_init(|| unreachable!()));
+}
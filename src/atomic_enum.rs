@@ -0,0 +1,126 @@
+/// Declares a fieldless enum together with an atomic cell holding one of its
+/// variants.
+///
+/// The cell is backed by an atomic `u8`, and its `load`, `store`, `swap` and
+/// `compare_exchange` methods take and return the enum itself. Only values
+/// of the enum are ever stored, so converting back from the raw byte needs
+/// no unchecked transmute, which is a common source of undefined behavior in
+/// hand-written versions.
+///
+/// The enum is given `#[repr(u8)]`, and its variants may have explicit
+/// discriminants.
+///
+/// # Examples
+/// ```
+/// use core::sync::atomic::Ordering;
+/// use skirt::atomic_enum;
+///
+/// atomic_enum! {
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///     pub enum Power: AtomicPower {
+///         Off,
+///         Sleep = 4,
+///         On,
+///     }
+/// }
+///
+/// static POWER: AtomicPower = AtomicPower::new(Power::Off);
+///
+/// POWER.store(Power::Sleep, Ordering::Release);
+/// assert_eq!(POWER.load(Ordering::Acquire), Power::Sleep);
+///
+/// assert_eq!(
+///     POWER.compare_exchange(Power::Sleep, Power::On, Ordering::AcqRel, Ordering::Acquire),
+///     Ok(Power::Sleep),
+/// );
+/// assert_eq!(POWER.swap(Power::Off, Ordering::AcqRel), Power::On);
+/// ```
+#[macro_export]
+macro_rules! atomic_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident: $atomic:ident {
+            $($(#[$variant_meta:meta])* $variant:ident $(= $value:expr)?),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(u8)]
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant $(= $value)?),+
+        }
+
+        #[doc = concat!("An atomic cell holding a [`", stringify!($name), "`].")]
+        #[repr(transparent)]
+        $vis struct $atomic($crate::__private::AtomicU8);
+
+        #[allow(dead_code)]
+        impl $atomic {
+            /// Creates a new cell holding `value`.
+            #[inline]
+            #[must_use]
+            $vis const fn new(value: $name) -> Self {
+                Self($crate::__private::AtomicU8::new(value as u8))
+            }
+
+            /// Loads the value.
+            #[inline]
+            $vis fn load(&self, order: $crate::__private::Ordering) -> $name {
+                Self::from_u8(self.0.load(order))
+            }
+
+            /// Stores a value.
+            #[inline]
+            $vis fn store(&self, value: $name, order: $crate::__private::Ordering) {
+                self.0.store(value as u8, order);
+            }
+
+            /// Stores a value, returning the previous one.
+            #[inline]
+            $vis fn swap(&self, value: $name, order: $crate::__private::Ordering) -> $name {
+                Self::from_u8(self.0.swap(value as u8, order))
+            }
+
+            /// Stores `new` if the current value is `current`.
+            ///
+            /// # Errors
+            /// Returns the current value if it was not `current`.
+            #[inline]
+            $vis fn compare_exchange(
+                &self,
+                current: $name,
+                new: $name,
+                success: $crate::__private::Ordering,
+                failure: $crate::__private::Ordering,
+            ) -> ::core::result::Result<$name, $name> {
+                self.0
+                    .compare_exchange(current as u8, new as u8, success, failure)
+                    .map(Self::from_u8)
+                    .map_err(Self::from_u8)
+            }
+
+            /// Consumes the cell, returning the value.
+            #[inline]
+            $vis fn into_inner(self) -> $name {
+                Self::from_u8(self.0.into_inner())
+            }
+
+            fn from_u8(value: u8) -> $name {
+                $(
+                    if value == $name::$variant as u8 {
+                        return $name::$variant;
+                    }
+                )+
+
+                ::core::unreachable!("only variants of the enum are stored")
+            }
+        }
+
+        impl ::core::fmt::Debug for $atomic {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(stringify!($atomic))
+                    .field(&(self.load($crate::__private::Ordering::Relaxed) as u8))
+                    .finish()
+            }
+        }
+    };
+}
@@ -0,0 +1,229 @@
+use crate::once_lock::OnceLock;
+use crate::sync::atomic::{AtomicU64, Ordering};
+use core::fmt::{Debug, Formatter};
+use std::time::{Duration, Instant};
+
+/// A [`Duration`] which can be safely shared between threads.
+///
+/// The duration is stored as whole nanoseconds in an atomic `u64`. Durations
+/// longer than `u64::MAX` nanoseconds, about 584 years, saturate.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::AtomicDuration;
+/// use std::time::Duration;
+///
+/// static BUSY: AtomicDuration = AtomicDuration::new(Duration::ZERO);
+///
+/// BUSY.fetch_add(Duration::from_millis(5));
+/// BUSY.fetch_add(Duration::from_millis(7));
+/// assert_eq!(BUSY.load(), Duration::from_millis(12));
+/// ```
+pub struct AtomicDuration {
+    nanos: AtomicU64,
+}
+
+impl AtomicDuration {
+    /// Creates a new atomic duration.
+    #[inline]
+    #[must_use]
+    pub const fn new(duration: Duration) -> Self {
+        Self {
+            nanos: AtomicU64::new(to_nanos(duration)),
+        }
+    }
+
+    /// Loads the duration.
+    #[inline]
+    #[must_use]
+    pub fn load(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Acquire))
+    }
+
+    /// Stores a duration.
+    #[inline]
+    pub fn store(&self, duration: Duration) {
+        self.nanos.store(to_nanos(duration), Ordering::Release);
+    }
+
+    /// Stores a duration, returning the previous one.
+    #[inline]
+    pub fn swap(&self, duration: Duration) -> Duration {
+        Duration::from_nanos(self.nanos.swap(to_nanos(duration), Ordering::AcqRel))
+    }
+
+    /// Adds to the duration, saturating on overflow, and returns the
+    /// previous one.
+    pub fn fetch_add(&self, duration: Duration) -> Duration {
+        let delta = to_nanos(duration);
+        let previous = self
+            .nanos
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |nanos| {
+                Some(nanos.saturating_add(delta))
+            })
+            .unwrap_or_else(|nanos| nanos);
+
+        Duration::from_nanos(previous)
+    }
+
+    /// Stores the longer of the current and the given duration, returning
+    /// the previous one.
+    #[inline]
+    pub fn fetch_max(&self, duration: Duration) -> Duration {
+        Duration::from_nanos(self.nanos.fetch_max(to_nanos(duration), Ordering::AcqRel))
+    }
+
+    /// Consumes the atomic and returns the contained duration.
+    #[inline]
+    #[must_use]
+    pub const fn into_inner(self) -> Duration {
+        Duration::from_nanos(self.nanos.into_inner())
+    }
+}
+
+impl Default for AtomicDuration {
+    fn default() -> Self {
+        Self::new(Duration::ZERO)
+    }
+}
+
+impl From<Duration> for AtomicDuration {
+    fn from(duration: Duration) -> Self {
+        Self::new(duration)
+    }
+}
+
+impl Debug for AtomicDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.load(), f)
+    }
+}
+
+/// An [`Instant`] which can be safely shared between threads.
+///
+/// This replaces a `Mutex<Instant>` for timestamps that are written often
+/// and read from other threads, such as the last heartbeat of a worker that
+/// a watchdog checks.
+///
+/// The instant is stored as nanoseconds since a time base, which is fixed
+/// the first time any `AtomicInstant` is used. Instants from before the time
+/// base saturate to it.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::AtomicInstant;
+/// use std::time::{Duration, Instant};
+///
+/// static HEARTBEAT: AtomicInstant = AtomicInstant::new();
+///
+/// // In the worker:
+/// HEARTBEAT.store_now();
+///
+/// // In the watchdog:
+/// assert!(HEARTBEAT.elapsed() < Duration::from_secs(60));
+/// assert!(HEARTBEAT.load() <= Instant::now());
+/// ```
+pub struct AtomicInstant {
+    nanos: AtomicU64,
+}
+
+impl AtomicInstant {
+    /// Creates a new atomic instant holding the time base.
+    ///
+    /// This is usable in statics. To start from the current time instead,
+    /// use [`now`](Self::now).
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a new atomic instant holding the current time.
+    #[must_use]
+    pub fn now() -> Self {
+        Self::from(Instant::now())
+    }
+
+    /// Loads the instant.
+    #[must_use]
+    pub fn load(&self) -> Instant {
+        from_nanos(self.nanos.load(Ordering::Acquire))
+    }
+
+    /// Stores an instant.
+    pub fn store(&self, instant: Instant) {
+        self.nanos.store(since_base(instant), Ordering::Release);
+    }
+
+    /// Stores the current time.
+    pub fn store_now(&self) {
+        self.store(Instant::now());
+    }
+
+    /// Stores an instant, returning the previous one.
+    pub fn swap(&self, instant: Instant) -> Instant {
+        from_nanos(self.nanos.swap(since_base(instant), Ordering::AcqRel))
+    }
+
+    /// Stores the later of the current and the given instant, returning the
+    /// previous one.
+    ///
+    /// Unlike [`store`](Self::store), this never moves the instant
+    /// backwards when several threads record timestamps at once.
+    pub fn fetch_max(&self, instant: Instant) -> Instant {
+        from_nanos(self.nanos.fetch_max(since_base(instant), Ordering::AcqRel))
+    }
+
+    /// Returns the time elapsed since the stored instant.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.load().elapsed()
+    }
+}
+
+impl Default for AtomicInstant {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Instant> for AtomicInstant {
+    fn from(instant: Instant) -> Self {
+        Self {
+            nanos: AtomicU64::new(since_base(instant)),
+        }
+    }
+}
+
+impl Debug for AtomicInstant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.load(), f)
+    }
+}
+
+static BASE: OnceLock<Instant> = OnceLock::new();
+
+fn base() -> Instant {
+    *BASE.get_or_init(Instant::now)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const fn to_nanos(duration: Duration) -> u64 {
+    let nanos = duration.as_nanos();
+
+    if nanos > u64::MAX as u128 {
+        u64::MAX
+    } else {
+        nanos as u64
+    }
+}
+
+fn since_base(instant: Instant) -> u64 {
+    to_nanos(instant.saturating_duration_since(base()))
+}
+
+fn from_nanos(nanos: u64) -> Instant {
+    base() + Duration::from_nanos(nanos)
+}
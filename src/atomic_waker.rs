@@ -0,0 +1,192 @@
+use crate::sync::atomic::{AtomicU8, Ordering};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Formatter};
+use core::task::Waker;
+
+/// No one is registering or waking.
+const IDLE: u8 = 0;
+/// A task is storing its waker.
+const REGISTERING: u8 = 1;
+/// A waker is being taken to be woken.
+const WAKING: u8 = 1 << 1;
+
+/// A slot for the [`Waker`] of the one task waiting on an event, which the
+/// signaling side can wake without locking.
+///
+/// A future [`register`]s the waker from its [`Context`] on every poll that
+/// returns [`Poll::Pending`], and the side that completes the event, such as
+/// an interrupt handler or another thread, calls [`wake`] after publishing
+/// it. Registering before checking for the event and waking after setting it
+/// guarantees that no wakeup is lost: either the poll sees the event, or the
+/// wake sees the waker.
+///
+/// Neither side ever blocks. If a wake races with a registration, the
+/// registering task is woken right away instead, and if two tasks register
+/// concurrently, one of the registrations is ignored, so only one task
+/// should wait on a given slot.
+///
+/// [`register`]: Self::register
+/// [`wake`]: Self::wake
+/// [`Context`]: core::task::Context
+/// [`Poll::Pending`]: core::task::Poll::Pending
+///
+/// # Examples
+/// ```
+/// use core::future::Future;
+/// use core::pin::Pin;
+/// use core::sync::atomic::{AtomicBool, Ordering};
+/// use core::task::{Context, Poll, Waker};
+/// use skirt::sync::AtomicWaker;
+///
+/// struct Signal {
+///     waker: AtomicWaker,
+///     set: AtomicBool,
+/// }
+///
+/// impl Signal {
+///     fn set(&self) {
+///         self.set.store(true, Ordering::Release);
+///         self.waker.wake();
+///     }
+/// }
+///
+/// struct Wait<'a>(&'a Signal);
+///
+/// impl Future for Wait<'_> {
+///     type Output = ();
+///
+///     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+///         self.0.waker.register(cx.waker());
+///
+///         if self.0.set.load(Ordering::Acquire) {
+///             Poll::Ready(())
+///         } else {
+///             Poll::Pending
+///         }
+///     }
+/// }
+///
+/// let signal = Signal {
+///     waker: AtomicWaker::new(),
+///     set: AtomicBool::new(false),
+/// };
+///
+/// let mut wait = Wait(&signal);
+/// let mut cx = Context::from_waker(Waker::noop());
+///
+/// assert!(Pin::new(&mut wait).poll(&mut cx).is_pending());
+/// signal.set();
+/// assert!(Pin::new(&mut wait).poll(&mut cx).is_ready());
+/// ```
+pub struct AtomicWaker {
+    state: AtomicU8,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+// SAFETY: The waker is only accessed by whoever moves the state out of
+// `IDLE`, and `Waker` is `Send` and `Sync`.
+unsafe impl Send for AtomicWaker {}
+// SAFETY: See above.
+unsafe impl Sync for AtomicWaker {}
+
+impl AtomicWaker {
+    /// Creates a new slot with no waker registered.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(IDLE),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    /// Registers `waker` to be woken by the next call to [`wake`].
+    ///
+    /// This replaces any previously registered waker, unless it would wake
+    /// the same task. If a wake is in progress, `waker` is woken right away
+    /// instead of being stored. Call this before checking whether the event
+    /// being waited on has happened.
+    ///
+    /// [`wake`]: Self::wake
+    pub fn register(&self, waker: &Waker) {
+        match self
+            .state
+            .compare_exchange(IDLE, REGISTERING, Ordering::Acquire, Ordering::Acquire)
+        {
+            Ok(_) => {
+                // SAFETY: The `REGISTERING` bit gives exclusive access to the
+                // waker until it is cleared.
+                let slot = unsafe { &mut *self.waker.get() };
+
+                if !slot.as_ref().is_some_and(|old| old.will_wake(waker)) {
+                    *slot = Some(waker.clone());
+                }
+
+                if let Err(state) = self.state.compare_exchange(
+                    REGISTERING,
+                    IDLE,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    // A wake came in while registering and left the waker to
+                    // us, so take it and wake it ourselves.
+                    debug_assert_eq!(state, REGISTERING | WAKING);
+
+                    let waker = slot.take();
+                    self.state.store(IDLE, Ordering::Release);
+
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            }
+            // A wake is in progress, so the event may already have happened.
+            Err(WAKING) => waker.wake_by_ref(),
+            // Another task is registering at the same time; only one of the
+            // registrations is kept.
+            Err(_) => {}
+        }
+    }
+
+    /// Wakes the registered task, if any.
+    ///
+    /// Call this after publishing the event being waited on. This never
+    /// blocks, so it may be called from an interrupt handler, as long as the
+    /// registered waker does not block either.
+    pub fn wake(&self) {
+        if let Some(waker) = self.take() {
+            waker.wake();
+        }
+    }
+
+    /// Takes the registered waker without waking it.
+    ///
+    /// Returns [`None`] if no waker is registered, or if a registration or
+    /// another wake is in progress; a concurrent registration wakes its own
+    /// task instead.
+    #[must_use]
+    pub fn take(&self) -> Option<Waker> {
+        match self.state.fetch_or(WAKING, Ordering::AcqRel) {
+            IDLE => {
+                // SAFETY: The `WAKING` bit gives exclusive access to the
+                // waker until it is cleared.
+                let waker = unsafe { (*self.waker.get()).take() };
+                self.state.fetch_and(!WAKING, Ordering::Release);
+                waker
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for AtomicWaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for AtomicWaker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AtomicWaker").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,385 @@
+use crate::cache_padded::shard_of;
+use crate::context::current_context;
+use crate::profile::Backoff;
+use crate::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use crate::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use core::fmt::{Debug, Display, Formatter};
+use core::num::NonZeroUsize;
+use core::ops::Deref;
+use core::ptr;
+
+/// The number of slots in the table of visible readers, which is shared by
+/// every [`BiasedRwLock`].
+const VISIBLE_READER_SLOTS: usize = 256;
+/// The number of reads through the shared counter after which a lock whose
+/// bias was revoked by a writer is biased towards readers again.
+const REBIAS_AFTER: usize = 1024;
+
+/// Readers admitted to a biased lock without touching its state word. Each
+/// slot holds the bias flag of the lock its reader is inside, or null.
+static VISIBLE_READERS: [AtomicPtr<AtomicBool>; VISIBLE_READER_SLOTS] =
+    [const { AtomicPtr::new(ptr::null_mut()) }; VISIBLE_READER_SLOTS];
+
+/// A reader-writer lock biased towards readers, so that read acquisition
+/// scales on many-core machines.
+///
+/// Under heavy read concurrency, the state word shared by every reader of an
+/// [`RwLock`] bounces between cores. While this lock is biased, a reader
+/// instead claims a slot in a global table of visible readers, picked from
+/// the lock's address and the executing [`context`], and never writes to the
+/// lock itself. A writer revokes the bias and waits for the readers in the
+/// table to leave, which makes writes slower. After a number of reads go
+/// through the shared counter again, the bias is restored.
+///
+/// A reader whose slot is taken by another lock or context falls back to the
+/// shared counter. Readers in the table do not wait for queued writers.
+///
+/// [`context`]: crate::sync::current_context
+///
+/// # Examples
+/// ```
+/// use skirt::sync::BiasedRwLock;
+///
+/// static ROUTES: BiasedRwLock<[u16; 4]> = BiasedRwLock::new([80, 443, 0, 0]);
+///
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| assert_eq!(ROUTES.read()[1], 443));
+///     }
+/// });
+///
+/// ROUTES.write()[2] = 8080;
+/// assert_eq!(ROUTES.read()[2], 8080);
+/// ```
+pub struct BiasedRwLock<T: ?Sized> {
+    biased: AtomicBool,
+    slow_reads: AtomicUsize,
+    lock: RwLock<T>,
+}
+
+impl<T> BiasedRwLock<T> {
+    /// Creates a new lock in an unlocked state, biased towards readers.
+    #[inline]
+    pub const fn new(data: T) -> Self {
+        Self {
+            biased: AtomicBool::new(true),
+            slow_reads: AtomicUsize::new(0),
+            lock: RwLock::new(data),
+        }
+    }
+
+    /// Consumes this lock, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.lock.into_inner()
+    }
+}
+
+impl<T: ?Sized> BiasedRwLock<T> {
+    /// Locks this lock with shared read access, blocking the current thread
+    /// until it can be acquired.
+    pub fn read(&self) -> BiasedRwLockReadGuard<'_, T> {
+        self.try_read_visible()
+            .unwrap_or_else(|| self.shared(self.lock.read()))
+    }
+
+    /// Attempts to acquire this lock with shared read access without
+    /// blocking.
+    ///
+    /// This fails under the same conditions as [`RwLock::try_read`], unless
+    /// the reader gets into the table of visible readers.
+    #[must_use]
+    pub fn try_read(&self) -> Option<BiasedRwLockReadGuard<'_, T>> {
+        self.try_read_visible()
+            .or_else(|| self.lock.try_read().map(|guard| self.shared(guard)))
+    }
+
+    /// Locks this lock with shared read access, ignoring waiting writers.
+    ///
+    /// See [`RwLock::read_recursive`]. This also holds while a writer waits
+    /// for the readers in the table of visible readers to leave.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::BiasedRwLock;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let lock = BiasedRwLock::new(1);
+    ///
+    /// thread::scope(|scope| {
+    ///     let outer = lock.read();
+    ///     scope.spawn(|| *lock.write() += 1);
+    ///
+    ///     // Let the writer revoke the bias and start waiting for `outer`.
+    ///     thread::sleep(Duration::from_millis(50));
+    ///
+    ///     let inner = lock.read_recursive();
+    ///     assert_eq!(*outer + *inner, 2);
+    /// });
+    ///
+    /// assert_eq!(*lock.read(), 2);
+    /// ```
+    pub fn read_recursive(&self) -> BiasedRwLockReadGuard<'_, T> {
+        self.try_read_visible()
+            .unwrap_or_else(|| self.shared(self.lock.read_recursive()))
+    }
+
+    /// Attempts to acquire this lock with shared read access, ignoring
+    /// waiting writers. See [`read_recursive`](Self::read_recursive).
+    #[must_use]
+    pub fn try_read_recursive(&self) -> Option<BiasedRwLockReadGuard<'_, T>> {
+        self.try_read_visible().or_else(|| {
+            self.lock
+                .try_read_recursive()
+                .map(|guard| self.shared(guard))
+        })
+    }
+
+    /// Locks this lock with exclusive write access, blocking the current
+    /// thread until it can be acquired.
+    ///
+    /// This revokes the bias and waits for the readers in the table of
+    /// visible readers to leave.
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        let mut backoff = Backoff::new();
+
+        loop {
+            let guard = self.lock.write();
+
+            if self.revoke_bias() {
+                return guard;
+            }
+
+            // Let the visible readers leave without holding the lock, so that
+            // they can still take it recursively.
+            drop(guard);
+
+            while self.visible_readers() != 0 {
+                self.lock.announce_writer();
+                backoff.relax();
+            }
+        }
+    }
+
+    /// Attempts to acquire this lock with exclusive write access without
+    /// blocking.
+    ///
+    /// This fails if the lock is held by a writer or any readers, including
+    /// readers in the table of visible readers. The bias is revoked either
+    /// way.
+    #[must_use]
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        let guard = self.lock.try_write()?;
+        self.revoke_bias().then_some(guard)
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `BiasedRwLock` mutably, no actual locking
+    /// needs to take place.
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.lock.get_mut()
+    }
+
+    /// Returns whether the lock is held by a writer or any readers.
+    ///
+    /// This scans the table of visible readers. It is only a hint and may be
+    /// outdated by the time it is used.
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.lock.is_locked() || self.visible_readers() != 0
+    }
+
+    /// Returns the number of readers holding the lock, including those in
+    /// the table of visible readers.
+    ///
+    /// Like [`is_locked`](Self::is_locked), this is only a hint.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::BiasedRwLock;
+    ///
+    /// let lock = BiasedRwLock::new(0);
+    ///
+    /// let a = lock.read();
+    /// let b = lock.read();
+    /// assert_eq!(lock.reader_count(), 2);
+    /// # drop((a, b));
+    /// ```
+    #[must_use]
+    pub fn reader_count(&self) -> usize {
+        self.lock.reader_count() + self.visible_readers()
+    }
+
+    /// Attempts to acquire shared read access through the table of visible
+    /// readers, which only succeeds while the lock is biased towards readers.
+    fn try_read_visible(&self) -> Option<BiasedRwLockReadGuard<'_, T>> {
+        if !self.biased.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let slot = self.slot();
+        let claimed = VISIBLE_READERS[slot]
+            .compare_exchange(
+                ptr::null_mut(),
+                self.key(),
+                Ordering::SeqCst,
+                Ordering::Relaxed,
+            )
+            .is_ok();
+
+        if !claimed {
+            return None;
+        }
+
+        // Check the bias again after claiming the slot, so that a writer
+        // revoking it either sees the slot or is seen here.
+        if !self.biased.load(Ordering::SeqCst) {
+            VISIBLE_READERS[slot].store(ptr::null_mut(), Ordering::Release);
+            return None;
+        }
+
+        Some(BiasedRwLockReadGuard {
+            lock: self,
+            reader: Reader::Visible(slot),
+        })
+    }
+
+    /// Wraps a read through the state word, biasing the lock towards readers
+    /// again after enough of them, unless a writer is waiting.
+    fn shared<'r>(&'r self, guard: RwLockReadGuard<'r, T>) -> BiasedRwLockReadGuard<'r, T> {
+        if !self.biased.load(Ordering::Relaxed)
+            && !self.lock.writers_waiting()
+            && self.slow_reads.fetch_add(1, Ordering::Relaxed) % REBIAS_AFTER == REBIAS_AFTER - 1
+        {
+            // A read lock is held, so no writer is inside to miss this.
+            self.biased.store(true, Ordering::Release);
+        }
+
+        BiasedRwLockReadGuard {
+            lock: self,
+            reader: Reader::Shared(guard),
+        }
+    }
+
+    /// Revokes the reader bias while the write lock is held, and returns
+    /// whether all readers in the table of visible readers have left.
+    ///
+    /// The bias stays revoked either way, so the caller can release the
+    /// write lock and retry once they are gone.
+    fn revoke_bias(&self) -> bool {
+        self.biased.store(false, Ordering::SeqCst);
+
+        let key = self.key();
+
+        VISIBLE_READERS
+            .iter()
+            .all(|slot| slot.load(Ordering::SeqCst) != key)
+    }
+
+    /// Returns the number of readers inside through the table of visible
+    /// readers.
+    fn visible_readers(&self) -> usize {
+        let key = self.key();
+
+        VISIBLE_READERS
+            .iter()
+            .filter(|slot| slot.load(Ordering::Relaxed) == key)
+            .count()
+    }
+
+    /// Picks the slot in the table of visible readers for this lock and the
+    /// executing context.
+    fn slot(&self) -> usize {
+        let context = current_context().map_or(0, NonZeroUsize::get);
+        shard_of(context ^ self.key().addr(), VISIBLE_READER_SLOTS)
+    }
+
+    /// Returns what identifies this lock in the table of visible readers.
+    const fn key(&self) -> *mut AtomicBool {
+        ptr::from_ref(&self.biased).cast_mut()
+    }
+}
+
+impl<T: Default> Default for BiasedRwLock<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for BiasedRwLock<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for BiasedRwLock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("BiasedRwLock");
+
+        match self.try_read() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+
+        d.finish_non_exhaustive()
+    }
+}
+
+/// How a [`BiasedRwLockReadGuard`] got into the lock.
+enum Reader<'r, T: ?Sized> {
+    /// Through this slot in the table of visible readers.
+    Visible(usize),
+    /// Through the state word of the underlying lock.
+    Shared(RwLockReadGuard<'r, T>),
+}
+
+/// RAII structure used to release the shared read access of a
+/// [`BiasedRwLock`] when dropped.
+///
+/// This structure is created by the [`read`] and [`try_read`] methods on
+/// [`BiasedRwLock`].
+///
+/// [`read`]: BiasedRwLock::read
+/// [`try_read`]: BiasedRwLock::try_read
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "writers spin on this lock while it is held"
+)]
+pub struct BiasedRwLockReadGuard<'r, T: ?Sized> {
+    lock: &'r BiasedRwLock<T>,
+    reader: Reader<'r, T>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for BiasedRwLockReadGuard<'_, T> {}
+
+impl<T: ?Sized> Deref for BiasedRwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: A read lock is held, so there is no writer.
+        unsafe { &*self.lock.lock.data_ptr() }
+    }
+}
+
+impl<T: ?Sized> Drop for BiasedRwLockReadGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // A guard of the underlying lock releases itself.
+        if let Reader::Visible(slot) = self.reader {
+            VISIBLE_READERS[slot].store(ptr::null_mut(), Ordering::Release);
+        }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for BiasedRwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for BiasedRwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
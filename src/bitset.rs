@@ -0,0 +1,170 @@
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A fixed-size set of bits that can be updated concurrently without locking.
+///
+/// The set holds `WORDS * usize::BITS` bits, all initially clear. Every
+/// operation is a single atomic read-modify-write on the word containing the
+/// bit, which makes it suitable for allocation bitmaps and ready flags shared
+/// with interrupt handlers.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::AtomicBitSet;
+///
+/// static SLOTS: AtomicBitSet<2> = AtomicBitSet::new();
+///
+/// assert_eq!(SLOTS.find_first_zero(), Some(0));
+/// assert!(!SLOTS.test_and_set(0));
+/// assert!(SLOTS.test_and_set(0));
+/// assert_eq!(SLOTS.find_first_zero(), Some(1));
+/// ```
+pub struct AtomicBitSet<const WORDS: usize> {
+    words: [AtomicUsize; WORDS],
+}
+
+impl<const WORDS: usize> AtomicBitSet<WORDS> {
+    /// The number of bits in the set.
+    pub const BITS: usize = WORDS * usize::BITS as usize;
+
+    /// Creates a new bitset with every bit clear.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            words: [const { AtomicUsize::new(0) }; WORDS],
+        }
+    }
+
+    /// Returns whether bit `index` is set.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`BITS`](Self::BITS).
+    #[must_use]
+    pub fn test(&self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        self.words[word].load(Ordering::Acquire) & mask != 0
+    }
+
+    /// Sets bit `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`BITS`](Self::BITS).
+    pub fn set(&self, index: usize) {
+        let (word, mask) = Self::locate(index);
+        self.words[word].fetch_or(mask, Ordering::AcqRel);
+    }
+
+    /// Clears bit `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`BITS`](Self::BITS).
+    pub fn clear(&self, index: usize) {
+        let (word, mask) = Self::locate(index);
+        self.words[word].fetch_and(!mask, Ordering::AcqRel);
+    }
+
+    /// Sets bit `index`, returning whether it was already set.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`BITS`](Self::BITS).
+    pub fn test_and_set(&self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        self.words[word].fetch_or(mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Clears bit `index`, returning whether it was set.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`BITS`](Self::BITS).
+    pub fn test_and_clear(&self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        self.words[word].fetch_and(!mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Returns the index of the lowest clear bit, or [`None`] if every bit is
+    /// set.
+    ///
+    /// The result is only a snapshot; another thread may set the bit before it
+    /// is used. To claim a bit, use [`set_first_zero`](Self::set_first_zero).
+    #[must_use]
+    pub fn find_first_zero(&self) -> Option<usize> {
+        self.words.iter().enumerate().find_map(|(i, word)| {
+            let bits = word.load(Ordering::Acquire);
+            (bits != usize::MAX).then(|| i * usize::BITS as usize + bits.trailing_ones() as usize)
+        })
+    }
+
+    /// Atomically claims the lowest clear bit, returning its index, or
+    /// [`None`] if every bit is set.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::AtomicBitSet;
+    ///
+    /// let set = AtomicBitSet::<1>::new();
+    /// set.set(0);
+    ///
+    /// assert_eq!(set.set_first_zero(), Some(1));
+    /// assert!(set.test(1));
+    /// ```
+    pub fn set_first_zero(&self) -> Option<usize> {
+        self.words.iter().enumerate().find_map(|(i, word)| {
+            let mut bits = word.load(Ordering::Relaxed);
+
+            while bits != usize::MAX {
+                let mask = 1 << bits.trailing_ones();
+
+                match word.compare_exchange_weak(
+                    bits,
+                    bits | mask,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        return Some(i * usize::BITS as usize + mask.trailing_zeros() as usize);
+                    }
+                    Err(current) => bits = current,
+                }
+            }
+
+            None
+        })
+    }
+
+    /// Clears every bit in the set.
+    pub fn clear_all(&self) {
+        for word in &self.words {
+            word.store(0, Ordering::Release);
+        }
+    }
+
+    #[inline]
+    const fn locate(index: usize) -> (usize, usize) {
+        assert!(index < Self::BITS, "bit index out of range");
+        (
+            index / usize::BITS as usize,
+            1 << (index % usize::BITS as usize),
+        )
+    }
+}
+
+impl<const WORDS: usize> Default for AtomicBitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> Debug for AtomicBitSet<WORDS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_set();
+
+        for index in 0..Self::BITS {
+            if self.test(index) {
+                d.entry(&index);
+            }
+        }
+
+        d.finish()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::biased_rwlock::BiasedRwLockReadGuard;
+use crate::brlock::{BrLockReadGuard, BrLockWriteGuard};
+use crate::fair_rwlock::{FairRwLockReadGuard, FairRwLockWriteGuard};
+use crate::leak_checked::LeakCheckedGuard;
+use crate::lock_array::LockArrayGuard;
+use crate::mutex::{MappedMutexGuard, MutexGuard, OnUnlockGuard};
+use crate::pool::PoolGuard;
+use crate::priority_mutex::PriorityMutexGuard;
+use crate::relax_strategy::RelaxStrategy;
+use crate::rwlock::{
+    MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLockOnUnlockGuard, RwLockReadGuard,
+    RwLockWriteGuard,
+};
+use crate::rwlock_policy::RwLockPolicy;
+#[cfg(feature = "std")]
+use crate::timed_hold::TimedHoldGuard;
+
+/// A marker for guards of locks that block by spinning.
+///
+/// Holding such a guard across an `.await` makes every other task that wants
+/// the lock spin until the holding task is polled again, which can stall or
+/// deadlock an executor. Code generic over guards can use this trait to
+/// reject them, for example in a wrapper that is only meant for async locks.
+///
+/// With the `nightly` feature, every guard in this crate is also marked
+/// `#[must_not_suspend]`, so enabling the lint in async code produces a
+/// diagnostic when a guard is held across a suspend point:
+///
+/// ```text
+/// #![feature(must_not_suspend)]
+/// #![warn(must_not_suspend)]
+/// ```
+///
+/// # Examples
+/// ```
+/// use skirt::sync::{BlockingGuard, Mutex};
+///
+/// fn assert_blocking<G: BlockingGuard>(_: &G) {}
+///
+/// let mutex = Mutex::new(0);
+/// assert_blocking(&mutex.lock());
+/// ```
+pub trait BlockingGuard {}
+
+impl<T: ?Sized, R: RelaxStrategy> BlockingGuard for MutexGuard<'_, T, R> {}
+impl<T: ?Sized> BlockingGuard for MappedMutexGuard<'_, T> {}
+impl<T: ?Sized, F: FnOnce(&mut T), R: RelaxStrategy> BlockingGuard for OnUnlockGuard<'_, T, F, R> {}
+impl<T: ?Sized> BlockingGuard for LeakCheckedGuard<'_, T> {}
+impl<T: ?Sized> BlockingGuard for PriorityMutexGuard<'_, T> {}
+impl<T: ?Sized, P: RwLockPolicy> BlockingGuard for RwLockReadGuard<'_, T, P> {}
+impl<T: ?Sized, P: RwLockPolicy> BlockingGuard for RwLockWriteGuard<'_, T, P> {}
+impl<T: ?Sized, F: FnOnce(&mut T), P: RwLockPolicy> BlockingGuard
+    for RwLockOnUnlockGuard<'_, T, F, P>
+{
+}
+impl<T: ?Sized> BlockingGuard for MappedRwLockReadGuard<'_, T> {}
+impl<T: ?Sized> BlockingGuard for MappedRwLockWriteGuard<'_, T> {}
+impl<T: ?Sized> BlockingGuard for BiasedRwLockReadGuard<'_, T> {}
+impl<T: ?Sized, const SHARDS: usize> BlockingGuard for BrLockReadGuard<'_, T, SHARDS> {}
+impl<T: ?Sized, const SHARDS: usize> BlockingGuard for BrLockWriteGuard<'_, T, SHARDS> {}
+impl<T: ?Sized> BlockingGuard for FairRwLockReadGuard<'_, T> {}
+impl<T: ?Sized> BlockingGuard for FairRwLockWriteGuard<'_, T> {}
+impl<T, const N: usize> BlockingGuard for PoolGuard<'_, T, N> {}
+impl<T, const N: usize> BlockingGuard for LockArrayGuard<'_, T, N> {}
+#[cfg(feature = "std")]
+impl<T: ?Sized> BlockingGuard for TimedHoldGuard<'_, T> {}
@@ -0,0 +1,291 @@
+use crate::cache_padded::{CachePadded, current_shard};
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+
+/// A "big reader" lock, optimized for data that is read far more often than
+/// it is written.
+///
+/// The reader count is sharded over `SHARDS` cache lines, and each reader
+/// only touches the shard picked by its [`current_context`] token. Readers on
+/// different cores therefore never contend with each other, at the cost of
+/// writers having to wait for every shard to drain.
+///
+/// Without a context (a `no_std` target with no registered
+/// [`CurrentContext`]), every reader uses the same shard.
+///
+/// [`current_context`]: crate::sync::current_context
+/// [`CurrentContext`]: crate::sync::CurrentContext
+///
+/// # Examples
+/// ```
+/// use skirt::sync::BrLock;
+///
+/// let lock = BrLock::<_>::new(5);
+///
+/// {
+///     let r1 = lock.read();
+///     let r2 = lock.read();
+///     assert_eq!(*r1 + *r2, 10);
+/// }
+///
+/// *lock.write() += 1;
+/// assert_eq!(*lock.read(), 6);
+/// ```
+pub struct BrLock<T: ?Sized, const SHARDS: usize = 8> {
+    writer: AtomicBool,
+    readers: [CachePadded<AtomicUsize>; SHARDS],
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send, const SHARDS: usize> Send for BrLock<T, SHARDS> {}
+unsafe impl<T: ?Sized + Send + Sync, const SHARDS: usize> Sync for BrLock<T, SHARDS> {}
+
+impl<T, const SHARDS: usize> BrLock<T, SHARDS> {
+    /// Creates a new lock in an unlocked state ready for use.
+    ///
+    /// # Panics
+    /// Panics if `SHARDS` is zero.
+    #[inline]
+    pub const fn new(data: T) -> Self {
+        assert!(SHARDS > 0, "a BrLock needs at least one shard");
+
+        Self {
+            writer: AtomicBool::new(false),
+            readers: [const { CachePadded::new(AtomicUsize::new(0)) }; SHARDS],
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consumes this lock, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized, const SHARDS: usize> BrLock<T, SHARDS> {
+    /// Locks this lock with shared read access, blocking the current thread
+    /// until it can be acquired.
+    pub fn read(&self) -> BrLockReadGuard<'_, T, SHARDS> {
+        let shard = Self::shard();
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Some(guard) = self.try_read_shard(shard) {
+                return guard;
+            }
+
+            while self.writer.load(Ordering::Relaxed) {
+                backoff.relax();
+            }
+        }
+    }
+
+    /// Attempts to acquire this lock with shared read access without
+    /// blocking.
+    #[must_use]
+    pub fn try_read(&self) -> Option<BrLockReadGuard<'_, T, SHARDS>> {
+        self.try_read_shard(Self::shard())
+    }
+
+    /// Locks this lock with exclusive write access, blocking the current
+    /// thread until it can be acquired.
+    pub fn write(&self) -> BrLockWriteGuard<'_, T, SHARDS> {
+        let mut backoff = Backoff::new();
+
+        while self
+            .writer
+            .compare_exchange_weak(false, true, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            backoff.relax();
+        }
+
+        // New readers now back off; wait for the ones already inside.
+        for readers in &self.readers {
+            while readers.load(Ordering::SeqCst) != 0 {
+                backoff.relax();
+            }
+        }
+
+        BrLockWriteGuard { lock: self }
+    }
+
+    /// Attempts to acquire this lock with exclusive write access without
+    /// blocking.
+    #[must_use]
+    pub fn try_write(&self) -> Option<BrLockWriteGuard<'_, T, SHARDS>> {
+        if self
+            .writer
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            return None;
+        }
+
+        if self
+            .readers
+            .iter()
+            .any(|readers| readers.load(Ordering::SeqCst) != 0)
+        {
+            self.writer.store(false, Ordering::Release);
+            return None;
+        }
+
+        Some(BrLockWriteGuard { lock: self })
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `BrLock` mutably, no actual locking needs
+    /// to take place.
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    fn try_read_shard(&self, shard: usize) -> Option<BrLockReadGuard<'_, T, SHARDS>> {
+        let readers = &self.readers[shard];
+
+        // Announce ourselves before checking for a writer, so that a writer
+        // either sees our count or we see its flag.
+        readers.fetch_add(1, Ordering::SeqCst);
+
+        if self.writer.load(Ordering::SeqCst) {
+            readers.fetch_sub(1, Ordering::Release);
+            return None;
+        }
+
+        Some(BrLockReadGuard { lock: self, shard })
+    }
+
+    fn shard() -> usize {
+        current_shard(SHARDS)
+    }
+}
+
+impl<T: Default, const SHARDS: usize> Default for BrLock<T, SHARDS> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T, const SHARDS: usize> From<T> for BrLock<T, SHARDS> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: ?Sized + Debug, const SHARDS: usize> Debug for BrLock<T, SHARDS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("BrLock");
+
+        match self.try_read() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+
+        d.finish_non_exhaustive()
+    }
+}
+
+/// RAII structure used to release the shared read access of a [`BrLock`]
+/// when dropped.
+///
+/// This structure is created by the [`read`] and [`try_read`] methods on
+/// [`BrLock`].
+///
+/// [`read`]: BrLock::read
+/// [`try_read`]: BrLock::try_read
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "writers spin while this read lock is held"
+)]
+pub struct BrLockReadGuard<'r, T: ?Sized, const SHARDS: usize = 8> {
+    lock: &'r BrLock<T, SHARDS>,
+    shard: usize,
+}
+
+unsafe impl<T: ?Sized + Sync, const SHARDS: usize> Sync for BrLockReadGuard<'_, T, SHARDS> {}
+
+impl<T: ?Sized, const SHARDS: usize> Deref for BrLockReadGuard<'_, T, SHARDS> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: A read lock is held, so no writer can access the data.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized, const SHARDS: usize> Drop for BrLockReadGuard<'_, T, SHARDS> {
+    #[inline]
+    fn drop(&mut self) {
+        self.lock.readers[self.shard].fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<T: ?Sized + Debug, const SHARDS: usize> Debug for BrLockReadGuard<'_, T, SHARDS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display, const SHARDS: usize> Display for BrLockReadGuard<'_, T, SHARDS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// RAII structure used to release the exclusive write access of a
+/// [`BrLock`] when dropped.
+///
+/// This structure is created by the [`write`] and [`try_write`] methods on
+/// [`BrLock`].
+///
+/// [`write`]: BrLock::write
+/// [`try_write`]: BrLock::try_write
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "every other task spins while this write lock is held"
+)]
+pub struct BrLockWriteGuard<'w, T: ?Sized, const SHARDS: usize = 8> {
+    lock: &'w BrLock<T, SHARDS>,
+}
+
+unsafe impl<T: ?Sized + Sync, const SHARDS: usize> Sync for BrLockWriteGuard<'_, T, SHARDS> {}
+
+impl<T: ?Sized, const SHARDS: usize> Deref for BrLockWriteGuard<'_, T, SHARDS> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The write lock is held, giving us exclusive access.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized, const SHARDS: usize> DerefMut for BrLockWriteGuard<'_, T, SHARDS> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The write lock is held, giving us exclusive access.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized, const SHARDS: usize> Drop for BrLockWriteGuard<'_, T, SHARDS> {
+    #[inline]
+    fn drop(&mut self) {
+        self.lock.writer.store(false, Ordering::Release);
+    }
+}
+
+impl<T: ?Sized + Debug, const SHARDS: usize> Debug for BrLockWriteGuard<'_, T, SHARDS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display, const SHARDS: usize> Display for BrLockWriteGuard<'_, T, SHARDS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::context::current_context;
+use core::ops::{Deref, DerefMut};
+
+/// Pads and aligns a value to the length of a cache line, so that values
+/// updated by different cores never share one.
+#[derive(Debug, Default)]
+#[repr(align(128))]
+pub struct CachePadded<T> {
+    value: T,
+}
+
+impl<T> CachePadded<T> {
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+}
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+/// Picks one of `shards` slots for the executing context, so that contexts
+/// on different cores tend to use different slots.
+pub fn current_shard(shards: usize) -> usize {
+    current_context().map_or(0, |token| shard_of(token.get(), shards))
+}
+
+/// Picks one of `shards` slots for `key`.
+pub const fn shard_of(key: usize, shards: usize) -> usize {
+    // Spread addresses and small core IDs alike over the shards with a
+    // Fibonacci hash.
+    (key.wrapping_mul(FIBONACCI) >> (usize::BITS / 2)) % shards
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const FIBONACCI: usize = 0x9E37_79B9_7F4A_7C15_u64 as usize;
@@ -0,0 +1,91 @@
+//! Fault injection for lock acquisition, for testing code that uses this
+//! crate.
+//!
+//! With the `chaos` feature, [`Mutex::try_lock`] fails at random even when
+//! the mutex is free, and [`Mutex::lock`] waits for a random extra delay
+//! before acquiring it. Locks built on [`Mutex`] inherit this behavior. This
+//! shakes out code that relies on timing, such as assuming that a `try_lock`
+//! right after an unlock succeeds, or checking state before locking instead
+//! of under the lock.
+//!
+//! Faults are only injected after [`seed`] is called, and until [`disable`]
+//! is, so enabling the feature for a whole test suite leaves the tests that
+//! do not opt in unaffected. The random decisions are derived from the seed,
+//! so a single-threaded test that fails can be replayed exactly.
+//!
+//! This feature is meant for tests only, and should never be enabled in
+//! production builds.
+//!
+//! [`Mutex`]: crate::sync::Mutex
+//! [`Mutex::try_lock`]: crate::sync::Mutex::try_lock
+//! [`Mutex::lock`]: crate::sync::Mutex::lock
+//!
+//! # Examples
+//! ```
+//! use skirt::sync::Mutex;
+//!
+//! skirt::chaos::seed(42);
+//!
+//! // Code under test must cope with `try_lock` failing on a free mutex.
+//! let mutex = Mutex::new(0);
+//! while mutex.try_lock().is_none() {}
+//!
+//! skirt::chaos::disable();
+//! assert!(mutex.try_lock().is_some());
+//! ```
+
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// One in this many calls to `try_lock` fails spuriously.
+const FAIL_ONE_IN: u32 = 4;
+
+/// The longest extra delay inserted by `lock`, in spin iterations.
+const MAX_DELAY_SPINS: u32 = 256;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SEED: AtomicUsize = AtomicUsize::new(0);
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the seed of the fault injection, restarts its sequence of decisions,
+/// and enables it.
+pub fn seed(seed: u32) {
+    SEED.store(seed as usize, Ordering::Relaxed);
+    COUNTER.store(0, Ordering::Relaxed);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stops injecting faults until [`seed`] is called again.
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Returns whether the current `try_lock` should fail spuriously.
+pub(crate) fn fail() -> bool {
+    ENABLED.load(Ordering::Relaxed) && next() % FAIL_ONE_IN == 0
+}
+
+/// Waits for a random extra delay before a `lock`.
+pub(crate) fn delay() {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    for _ in 0..next() % MAX_DELAY_SPINS {
+        core::hint::spin_loop();
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn next() -> u32 {
+    let seed = SEED.load(Ordering::Relaxed) as u32;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed) as u32;
+
+    // The finalizer of MurmurHash3, which turns consecutive counts into
+    // well-spread values.
+    let mut x = seed ^ count.wrapping_mul(0x9E37_79B9);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x85EB_CA6B);
+    x ^= x >> 13;
+    x = x.wrapping_mul(0xC2B2_AE35);
+    x ^ (x >> 16)
+}
@@ -0,0 +1,126 @@
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A kernel-style completion, signalling that an operation has finished.
+///
+/// Each [`complete`] lets exactly one [`wait`] through, now or later, while
+/// [`complete_all`] lets every current and future waiter through until the
+/// completion is [`reinit`]ialized. Signalling never spins or blocks, so
+/// driver code can complete an operation from an interrupt handler while
+/// task code waits for it.
+///
+/// [`complete`]: Self::complete
+/// [`complete_all`]: Self::complete_all
+/// [`wait`]: Self::wait
+/// [`reinit`]: Self::reinit
+///
+/// # Examples
+/// ```
+/// use skirt::sync::Completion;
+/// use std::thread;
+///
+/// static TRANSFER_DONE: Completion = Completion::new();
+///
+/// // In the interrupt handler:
+/// let handle = thread::spawn(|| TRANSFER_DONE.complete());
+///
+/// // In task code:
+/// TRANSFER_DONE.wait();
+/// assert!(!TRANSFER_DONE.try_wait());
+/// # handle.join().unwrap();
+/// ```
+pub struct Completion {
+    done: AtomicUsize,
+}
+
+impl Completion {
+    const ALL: usize = usize::MAX;
+
+    /// A completion that has not been completed, usable where a constant is
+    /// needed, such as in array repeat expressions.
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const INIT: Self = Self::new();
+
+    /// Creates a new completion that has not been completed.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            done: AtomicUsize::new(0),
+        }
+    }
+
+    /// Lets one waiter through.
+    ///
+    /// This never spins and is safe to call from interrupt context.
+    pub fn complete(&self) {
+        let _ = self
+            .done
+            .fetch_update(Ordering::Release, Ordering::Relaxed, |done| {
+                (done != Self::ALL).then(|| done.saturating_add(1).min(Self::ALL - 1))
+            });
+    }
+
+    /// Lets every current and future waiter through, until
+    /// [`reinit`](Self::reinit) is called.
+    ///
+    /// This never spins and is safe to call from interrupt context.
+    pub fn complete_all(&self) {
+        self.done.store(Self::ALL, Ordering::Release);
+    }
+
+    /// Consumes one completion if there is one, without blocking, returning
+    /// whether it did.
+    #[must_use]
+    pub fn try_wait(&self) -> bool {
+        self.done
+            .fetch_update(Ordering::Acquire, Ordering::Acquire, |done| match done {
+                0 => None,
+                Self::ALL => Some(Self::ALL),
+                done => Some(done - 1),
+            })
+            .is_ok()
+    }
+
+    /// Blocks the current thread until the completion is completed, then
+    /// consumes one completion.
+    ///
+    /// This must not be called from interrupt context.
+    pub fn wait(&self) {
+        let mut backoff = Backoff::new();
+
+        while !self.try_wait() {
+            backoff.relax();
+        }
+    }
+
+    /// Returns whether a call to [`wait`](Self::wait) would currently
+    /// return without blocking.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Relaxed) != 0
+    }
+
+    /// Resets the completion to not completed, discarding any pending
+    /// completions.
+    pub fn reinit(&self) {
+        self.done.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Default for Completion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Completion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Completion")
+            .field("done", &self.is_done())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,90 @@
+use crate::once_lock::OnceLock;
+use core::num::NonZeroUsize;
+
+/// Identifies the thread, task, or core that is currently executing.
+///
+/// Features that need to know *who* holds a lock, such as reentrancy and
+/// recursive-lock checks, ask the registered context for a token. On `std`
+/// targets the current thread is used when no context has been registered.
+/// On `no_std` targets, register an implementation once at startup with
+/// [`set_current_context`].
+///
+/// # Examples
+/// ```
+/// use core::num::NonZeroUsize;
+/// use skirt::sync::{CurrentContext, current_context, set_current_context};
+///
+/// struct Core;
+///
+/// impl CurrentContext for Core {
+///     fn current(&self) -> NonZeroUsize {
+///         // e.g. read the core ID register and offset it by one.
+///         NonZeroUsize::MIN
+///     }
+/// }
+///
+/// static CORE: Core = Core;
+///
+/// set_current_context(&CORE).ok().expect("context already registered");
+/// assert_eq!(current_context(), Some(NonZeroUsize::MIN));
+/// ```
+pub trait CurrentContext: Sync {
+    /// Returns a non-zero token that is unique to the executing context for
+    /// as long as it runs.
+    fn current(&self) -> NonZeroUsize;
+
+    /// Returns the scheduling priority of the executing context, where higher
+    /// values are more urgent.
+    ///
+    /// The default implementation treats every context as priority `0`.
+    fn priority(&self) -> u8 {
+        0
+    }
+}
+
+static CONTEXT: OnceLock<&'static dyn CurrentContext> = OnceLock::new();
+
+/// Registers the provider used by [`current_context`].
+///
+/// # Errors
+/// A context can only be registered once. If one is already registered,
+/// `context` is returned back.
+pub fn set_current_context(
+    context: &'static dyn CurrentContext,
+) -> Result<(), &'static dyn CurrentContext> {
+    CONTEXT.set(context)
+}
+
+/// Returns the token of the executing context.
+///
+/// The registered [`CurrentContext`] is used if there is one. Otherwise, on
+/// `std` targets a token identifying the current thread is returned, and on
+/// `no_std` targets [`None`] is returned.
+#[must_use]
+pub fn current_context() -> Option<NonZeroUsize> {
+    CONTEXT
+        .get()
+        .map_or_else(thread_token, |context| Some(context.current()))
+}
+
+#[cfg(feature = "std")]
+fn thread_token() -> Option<NonZeroUsize> {
+    std::thread_local! {
+        static TOKEN: u8 = const { 0 };
+    }
+
+    // The address of a thread-local is unique among live threads.
+    TOKEN.with(|token| NonZeroUsize::new(core::ptr::from_ref(token).addr()))
+}
+
+#[cfg(not(feature = "std"))]
+const fn thread_token() -> Option<NonZeroUsize> {
+    None
+}
+
+/// Returns the priority of the executing context, as reported by the
+/// registered [`CurrentContext`], or `0` if none is registered.
+#[must_use]
+pub fn current_priority() -> u8 {
+    CONTEXT.get().map_or(0, |context| context.priority())
+}
@@ -0,0 +1,120 @@
+use crate::mutex::Mutex;
+use crate::rwlock::RwLock;
+use alloc::sync::Arc;
+use core::fmt::{Debug, Formatter};
+
+/// A copy-on-write cell for read-mostly data.
+///
+/// Readers take a cheap snapshot of the current value with [`read`], which
+/// stays valid and unchanged for as long as it is held. Taking a snapshot
+/// only holds a shared lock for as long as it takes to clone an [`Arc`], so
+/// readers never exclude each other. Writers never modify
+/// a value in place: [`write`] clones the current value, mutates the clone,
+/// and then publishes it, so readers are never blocked for the duration of
+/// an update. Writers are serialized with each other.
+///
+/// This suits medium-sized configuration or routing tables that are read on
+/// every operation but updated rarely.
+///
+/// [`read`]: Self::read
+/// [`write`]: Self::write
+///
+/// # Examples
+/// ```
+/// use skirt::sync::CowLock;
+///
+/// let routes = CowLock::new(vec!["a", "b"]);
+///
+/// let snapshot = routes.read();
+/// routes.write(|routes| routes.push("c"));
+///
+/// assert_eq!(*snapshot, ["a", "b"]);
+/// assert_eq!(*routes.read(), ["a", "b", "c"]);
+/// ```
+pub struct CowLock<T> {
+    current: RwLock<Arc<T>>,
+    writer: Mutex<()>,
+}
+
+impl<T> CowLock<T> {
+    /// Creates a new cell holding `data`.
+    #[inline]
+    pub fn new(data: T) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(data)),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Returns a snapshot of the current value.
+    ///
+    /// Later writes publish new values and leave the snapshot untouched.
+    #[must_use]
+    pub fn read(&self) -> Arc<T> {
+        Arc::clone(&self.current.read())
+    }
+
+    /// Replaces the current value with `data`, returning the previous one.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::CowLock;
+    ///
+    /// let cell = CowLock::new(1);
+    /// assert_eq!(*cell.replace(2), 1);
+    /// assert_eq!(*cell.read(), 2);
+    /// ```
+    pub fn replace(&self, data: T) -> Arc<T> {
+        let _writer = self.writer.lock();
+        self.publish(Arc::new(data))
+    }
+
+    /// Consumes this cell, returning the current value.
+    ///
+    /// The value is shared with any snapshots that are still alive.
+    pub fn into_inner(self) -> Arc<T> {
+        self.current.into_inner()
+    }
+
+    fn publish(&self, data: Arc<T>) -> Arc<T> {
+        // The previous value is dropped by the caller, outside of the lock.
+        core::mem::replace(&mut *self.current.write(), data)
+    }
+}
+
+impl<T: Clone> CowLock<T> {
+    /// Clones the current value, mutates the clone with `f`, and publishes it.
+    ///
+    /// Readers keep seeing the previous value until `f` returns. Concurrent
+    /// writers wait for each other, so no update is lost.
+    pub fn write<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let _writer = self.writer.lock();
+
+        let mut data = T::clone(&self.read());
+        let result = f(&mut data);
+        drop(self.publish(Arc::new(data)));
+
+        result
+    }
+}
+
+impl<T: Default> Default for CowLock<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for CowLock<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: Debug> Debug for CowLock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("CowLock").field(&self.read()).finish()
+    }
+}
@@ -0,0 +1,105 @@
+use crate::error::TimeoutError;
+use std::time::{Duration, Instant};
+
+/// A point in time by which a multi-step operation must complete.
+///
+/// Passing the same deadline to every timed acquisition of an operation,
+/// such as [`Mutex::lock_until`] or [`RwLock::write_until`], makes the steps
+/// share a single time budget, instead of each step getting a fresh timeout
+/// and the whole operation taking up to their sum.
+///
+/// [`Mutex::lock_until`]: crate::sync::Mutex::lock_until
+/// [`RwLock::write_until`]: crate::sync::RwLock::write_until
+///
+/// # Examples
+/// ```
+/// use skirt::sync::{Deadline, Mutex, RwLock};
+/// use std::time::Duration;
+///
+/// let index = RwLock::new(vec![1, 2]);
+/// let log = Mutex::new(Vec::new());
+///
+/// let deadline = Deadline::after(Duration::from_millis(50));
+///
+/// let mut index = index.write_until(&deadline).unwrap();
+/// let mut log = log.lock_until(&deadline).unwrap();
+///
+/// index.push(3);
+/// log.push("inserted 3");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Deadline {
+    at: Instant,
+}
+
+impl Deadline {
+    /// Creates a deadline at `at`.
+    #[inline]
+    #[must_use]
+    pub const fn at(at: Instant) -> Self {
+        Self { at }
+    }
+
+    /// Creates a deadline `timeout` from now, saturating far in the future.
+    #[must_use]
+    pub fn after(timeout: Duration) -> Self {
+        let now = Instant::now();
+
+        Self {
+            at: now
+                .checked_add(timeout)
+                .unwrap_or_else(|| now + Duration::from_secs(u64::from(u32::MAX))),
+        }
+    }
+
+    /// Returns the instant of this deadline.
+    #[inline]
+    #[must_use]
+    pub const fn instant(&self) -> Instant {
+        self.at
+    }
+
+    /// Returns the time left until the deadline, or zero if it has passed.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Deadline;
+    /// use std::time::Duration;
+    ///
+    /// let deadline = Deadline::after(Duration::from_secs(60));
+    /// assert!(deadline.remaining() > Duration::from_secs(59));
+    ///
+    /// let deadline = Deadline::after(Duration::ZERO);
+    /// assert_eq!(deadline.remaining(), Duration::ZERO);
+    /// ```
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_duration_since(Instant::now())
+    }
+
+    /// Returns whether the deadline has passed.
+    #[must_use]
+    pub fn has_passed(&self) -> bool {
+        Instant::now() >= self.at
+    }
+
+    /// Fails with a [`TimeoutError`] if the deadline has passed, which lets
+    /// steps that do not acquire a lock share the budget too.
+    ///
+    /// # Errors
+    /// Returns [`TimeoutError`] if the deadline has passed.
+    #[inline]
+    pub fn check(&self) -> Result<(), TimeoutError> {
+        if self.has_passed() {
+            Err(TimeoutError)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<Instant> for Deadline {
+    fn from(at: Instant) -> Self {
+        Self::at(at)
+    }
+}
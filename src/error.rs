@@ -0,0 +1,152 @@
+use core::fmt::{Debug, Display, Formatter};
+
+/// The error returned when a lock could not be acquired without blocking.
+///
+/// Like the standard library's, this also reports a poisoned lock, handing
+/// back its guard. Without the `poison` feature, locks are never poisoned.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::{Mutex, MutexGuard, TryLockError};
+///
+/// fn bump(mutex: &Mutex<u32>) -> Result<(), TryLockError<MutexGuard<'_, u32>>> {
+///     *mutex.try_lock_checked()? += 1;
+///     Ok(())
+/// }
+///
+/// let mutex = Mutex::new(0);
+/// assert!(bump(&mutex).is_ok());
+///
+/// let guard = mutex.lock();
+/// assert!(matches!(bump(&mutex), Err(TryLockError::WouldBlock)));
+/// # drop(guard);
+/// ```
+pub enum TryLockError<G> {
+    /// The lock is poisoned, but was acquired anyway.
+    Poisoned(PoisonError<G>),
+    /// The lock is held, and acquiring it would block.
+    WouldBlock,
+}
+
+impl<G> From<PoisonError<G>> for TryLockError<G> {
+    #[inline]
+    fn from(error: PoisonError<G>) -> Self {
+        Self::Poisoned(error)
+    }
+}
+
+impl<G> Debug for TryLockError<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Poisoned(error) => f.debug_tuple("Poisoned").field(error).finish(),
+            Self::WouldBlock => f.write_str("WouldBlock"),
+        }
+    }
+}
+
+impl<G> Display for TryLockError<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Poisoned(error) => Display::fmt(error, f),
+            Self::WouldBlock => f.write_str("lock is held and acquiring it would block"),
+        }
+    }
+}
+
+impl<G> core::error::Error for TryLockError<G> {}
+
+/// The error returned when a timed operation did not complete before its
+/// deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeoutError;
+
+impl Display for TimeoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("operation timed out")
+    }
+}
+
+impl core::error::Error for TimeoutError {}
+
+/// The error returned when an operation gave up after spinning for its
+/// budget of attempts.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::SpinBudgetExceeded;
+///
+/// let error = SpinBudgetExceeded { spins: 100 };
+/// assert_eq!(error.to_string(), "gave up after spinning 100 times");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpinBudgetExceeded {
+    /// The number of attempts that were made.
+    pub spins: u32,
+}
+
+impl Display for SpinBudgetExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "gave up after spinning {} times", self.spins)
+    }
+}
+
+impl core::error::Error for SpinBudgetExceeded {}
+
+/// The error returned when a lock was acquired, but is poisoned because a
+/// previous holder panicked.
+///
+/// The guard is still available through the error, since the data may well
+/// be usable or repairable.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::{Mutex, PoisonError};
+///
+/// let mutex = Mutex::new(1);
+/// let error = PoisonError::new(mutex.lock());
+///
+/// assert_eq!(*error.into_inner(), 1);
+/// ```
+pub struct PoisonError<G> {
+    guard: G,
+}
+
+impl<G> PoisonError<G> {
+    /// Creates a poison error holding `guard`.
+    #[inline]
+    pub const fn new(guard: G) -> Self {
+        Self { guard }
+    }
+
+    /// Consumes this error, returning the guard.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.guard
+    }
+
+    /// Returns a reference to the guard.
+    #[inline]
+    pub const fn get_ref(&self) -> &G {
+        &self.guard
+    }
+
+    /// Returns a mutable reference to the guard.
+    #[inline]
+    pub const fn get_mut(&mut self) -> &mut G {
+        &mut self.guard
+    }
+}
+
+impl<G> Debug for PoisonError<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PoisonError").finish_non_exhaustive()
+    }
+}
+
+impl<G> Display for PoisonError<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("lock is poisoned because a previous holder panicked")
+    }
+}
+
+impl<G> core::error::Error for PoisonError<G> {}
@@ -0,0 +1,175 @@
+use crate::once::Once;
+use crate::profile::Backoff;
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Formatter};
+use core::mem::MaybeUninit;
+
+/// A cell where exactly one caller at a time wins the right to produce its
+/// value, and may give that right back.
+///
+/// [`begin`] hands the winner an [`Initializer`], while other callers wait
+/// until it either [`finish`]es or [`abort`]s. Aborting returns the cell to
+/// its empty state so that the next caller can retry, which suits fallible
+/// bring-up sequences such as hardware initialization.
+///
+/// [`begin`]: Self::begin
+/// [`finish`]: Initializer::finish
+/// [`abort`]: Initializer::abort
+///
+/// # Examples
+/// ```
+/// use skirt::sync::ExclusiveOnce;
+///
+/// static DEVICE: ExclusiveOnce<u32> = ExclusiveOnce::new();
+///
+/// // The first attempt fails and gives up its claim.
+/// let init = DEVICE.begin().unwrap();
+/// init.abort();
+/// assert_eq!(DEVICE.get(), None);
+///
+/// // The retry succeeds, after which nobody else may initialize.
+/// let init = DEVICE.begin().unwrap();
+/// assert_eq!(*init.finish(7), 7);
+/// assert!(DEVICE.begin().is_none());
+/// assert_eq!(DEVICE.get(), Some(&7));
+/// ```
+pub struct ExclusiveOnce<T> {
+    once: Once,
+    data: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Sync + Send> Sync for ExclusiveOnce<T> {}
+unsafe impl<T: Send> Send for ExclusiveOnce<T> {}
+
+impl<T> ExclusiveOnce<T> {
+    /// Creates a new empty cell.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            once: Once::new(),
+            data: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Claims the right to initialize the cell.
+    ///
+    /// Returns [`None`] if the cell is already initialized. If another caller
+    /// holds the [`Initializer`], this blocks until it finishes, in which case
+    /// [`None`] is returned, or aborts, in which case this caller may claim it.
+    pub fn begin(&self) -> Option<Initializer<'_, T>> {
+        loop {
+            if self.once.try_begin() {
+                return Some(Initializer { cell: self });
+            }
+
+            if self.once.wait() {
+                return None;
+            }
+        }
+    }
+
+    /// Returns the value if the cell has been initialized.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.once
+            .is_completed()
+            .then(|| unsafe { (*self.data.get()).assume_init_ref() })
+    }
+
+    /// Returns a mutable reference to the value if the cell has been
+    /// initialized.
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.once
+            .is_completed()
+            .then(|| unsafe { (*self.data.get()).assume_init_mut() })
+    }
+
+    /// Blocks until the cell has been initialized, returning the value.
+    ///
+    /// Unlike [`begin`](Self::begin), this never claims the right to
+    /// initialize the cell, and keeps waiting across aborted attempts.
+    pub fn wait(&self) -> &T {
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Some(data) = self.get() {
+                return data;
+            }
+
+            backoff.relax();
+        }
+    }
+}
+
+impl<T> Default for ExclusiveOnce<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for ExclusiveOnce<T> {
+    fn drop(&mut self) {
+        if self.once.is_completed() {
+            unsafe { (*self.data.get()).assume_init_drop() };
+        }
+    }
+}
+
+impl<T: Debug> Debug for ExclusiveOnce<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_tuple("ExclusiveOnce");
+
+        match self.get() {
+            Some(v) => d.field(v),
+            None => d.field(&format_args!("<uninit>")),
+        };
+
+        d.finish()
+    }
+}
+
+/// The exclusive right to initialize an [`ExclusiveOnce`].
+///
+/// Dropping the initializer without calling [`finish`](Self::finish) aborts
+/// the attempt, as if [`abort`](Self::abort) had been called.
+///
+/// This structure is created by the [`begin`] method on [`ExclusiveOnce`].
+///
+/// [`begin`]: ExclusiveOnce::begin
+#[must_use = "dropping the initializer aborts the initialization"]
+pub struct Initializer<'a, T> {
+    cell: &'a ExclusiveOnce<T>,
+}
+
+impl<'a, T> Initializer<'a, T> {
+    /// Stores `value` in the cell and wakes any waiting callers.
+    pub fn finish(self, value: T) -> &'a T {
+        let cell = self.cell;
+        core::mem::forget(self);
+
+        // SAFETY: We hold the exclusive right to initialize the cell.
+        let data = unsafe { (*cell.data.get()).write(value) };
+        cell.once.finish();
+        data
+    }
+
+    /// Gives up the right to initialize, returning the cell to its empty
+    /// state.
+    pub fn abort(self) {
+        drop(self);
+    }
+}
+
+impl<T> Drop for Initializer<'_, T> {
+    fn drop(&mut self) {
+        self.cell.once.abort();
+    }
+}
+
+impl<T> Debug for Initializer<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Initializer").finish_non_exhaustive()
+    }
+}
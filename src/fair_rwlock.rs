@@ -0,0 +1,324 @@
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+
+/// Set in the reader entry counter while a writer is present.
+const PRESENT: usize = 0b10;
+/// The phase of the present writer, alternating between writers.
+const PHASE: usize = 0b01;
+/// The writer bits of the reader entry counter.
+const WRITER_BITS: usize = PRESENT | PHASE;
+/// The amount each reader adds to the reader counters.
+const READER: usize = 1 << 8;
+
+/// A phase-fair reader-writer lock.
+///
+/// Readers and writers take turns in phases: a reader arriving while a writer
+/// is present waits for exactly that writer, and a writer waits only for the
+/// readers already inside and the writers ahead of it, which are served in
+/// FIFO order. Neither side can starve the other under sustained contention,
+/// and the wait of any acquisition is bounded, which suits latency-sensitive
+/// servers.
+///
+/// Compared to [`RwLock`], acquisitions are a little more expensive, and
+/// readers cannot overtake a waiting writer.
+///
+/// [`RwLock`]: crate::sync::RwLock
+///
+/// # Examples
+/// ```
+/// use skirt::sync::FairRwLock;
+///
+/// let lock = FairRwLock::new(5);
+///
+/// {
+///     let r1 = lock.read();
+///     let r2 = lock.read();
+///     assert_eq!(*r1 + *r2, 10);
+/// }
+///
+/// *lock.write() += 1;
+/// assert_eq!(*lock.read(), 6);
+/// ```
+pub struct FairRwLock<T: ?Sized> {
+    reader_in: AtomicUsize,
+    reader_out: AtomicUsize,
+    writer_in: AtomicUsize,
+    writer_out: AtomicUsize,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for FairRwLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for FairRwLock<T> {}
+
+impl<T> FairRwLock<T> {
+    /// Creates a new lock in an unlocked state ready for use.
+    #[inline]
+    pub const fn new(data: T) -> Self {
+        Self {
+            reader_in: AtomicUsize::new(0),
+            reader_out: AtomicUsize::new(0),
+            writer_in: AtomicUsize::new(0),
+            writer_out: AtomicUsize::new(0),
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consumes this lock, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> FairRwLock<T> {
+    /// Locks this lock with shared read access, blocking the current thread
+    /// until it can be acquired.
+    ///
+    /// If a writer is present, this waits for that writer to release the
+    /// lock, but not for any writers queued behind it.
+    pub fn read(&self) -> FairRwLockReadGuard<'_, T> {
+        let writer = self.reader_in.fetch_add(READER, Ordering::Acquire) & WRITER_BITS;
+
+        if writer != 0 {
+            // Wait until the writer that was present leaves, which changes
+            // the bits even if the next writer is already present.
+            let mut backoff = Backoff::new();
+
+            while self.reader_in.load(Ordering::Acquire) & WRITER_BITS == writer {
+                backoff.relax();
+            }
+        }
+
+        FairRwLockReadGuard { lock: self }
+    }
+
+    /// Attempts to acquire this lock with shared read access without
+    /// blocking.
+    ///
+    /// This fails if a writer is present.
+    #[must_use]
+    pub fn try_read(&self) -> Option<FairRwLockReadGuard<'_, T>> {
+        let mut state = self.reader_in.load(Ordering::Relaxed);
+
+        loop {
+            if state & WRITER_BITS != 0 {
+                return None;
+            }
+
+            match self.reader_in.compare_exchange_weak(
+                state,
+                state.wrapping_add(READER),
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(FairRwLockReadGuard { lock: self }),
+                Err(current) => state = current,
+            }
+        }
+    }
+
+    /// Locks this lock with exclusive write access, blocking the current
+    /// thread until it can be acquired.
+    ///
+    /// Writers are served in the order they arrive.
+    pub fn write(&self) -> FairRwLockWriteGuard<'_, T> {
+        let ticket = self.writer_in.fetch_add(1, Ordering::Relaxed);
+        let mut backoff = Backoff::new();
+
+        while self.writer_out.load(Ordering::Acquire) != ticket {
+            backoff.relax();
+        }
+
+        // Keep new readers out, and wait for the ones already inside.
+        let bits = PRESENT | (ticket & PHASE);
+        let readers = self.reader_in.fetch_add(bits, Ordering::Relaxed) & !WRITER_BITS;
+
+        while self.reader_out.load(Ordering::Acquire) != readers {
+            backoff.relax();
+        }
+
+        FairRwLockWriteGuard { lock: self }
+    }
+
+    /// Attempts to acquire this lock with exclusive write access without
+    /// blocking.
+    ///
+    /// This fails if the lock is held, or if other writers are queued.
+    #[must_use]
+    pub fn try_write(&self) -> Option<FairRwLockWriteGuard<'_, T>> {
+        let ticket = self.writer_out.load(Ordering::Acquire);
+        let readers = self.reader_in.load(Ordering::Relaxed);
+
+        if readers != self.reader_out.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        self.writer_in
+            .compare_exchange(
+                ticket,
+                ticket.wrapping_add(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .ok()?;
+
+        // It is our turn now, but a reader may have arrived meanwhile.
+        let bits = PRESENT | (ticket & PHASE);
+
+        if self
+            .reader_in
+            .compare_exchange(
+                readers,
+                readers | bits,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            // Pass the turn on without ever having kept readers out.
+            self.writer_out.fetch_add(1, Ordering::Release);
+            return None;
+        }
+
+        Some(FairRwLockWriteGuard { lock: self })
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `FairRwLock` mutably, no actual locking
+    /// needs to take place.
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+}
+
+impl<T: Default> Default for FairRwLock<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for FairRwLock<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for FairRwLock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("FairRwLock");
+
+        match self.try_read() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+
+        d.finish_non_exhaustive()
+    }
+}
+
+/// RAII structure used to release the shared read access of a
+/// [`FairRwLock`] when dropped.
+///
+/// This structure is created by the [`read`] and [`try_read`] methods on
+/// [`FairRwLock`].
+///
+/// [`read`]: FairRwLock::read
+/// [`try_read`]: FairRwLock::try_read
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "writers spin while this read lock is held"
+)]
+pub struct FairRwLockReadGuard<'r, T: ?Sized> {
+    lock: &'r FairRwLock<T>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for FairRwLockReadGuard<'_, T> {}
+
+impl<T: ?Sized> Deref for FairRwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: A read lock is held, so no writer can access the data.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for FairRwLockReadGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.lock.reader_out.fetch_add(READER, Ordering::Release);
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for FairRwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for FairRwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// RAII structure used to release the exclusive write access of a
+/// [`FairRwLock`] when dropped.
+///
+/// This structure is created by the [`write`] and [`try_write`] methods on
+/// [`FairRwLock`].
+///
+/// [`write`]: FairRwLock::write
+/// [`try_write`]: FairRwLock::try_write
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "every other task spins while this write lock is held"
+)]
+pub struct FairRwLockWriteGuard<'w, T: ?Sized> {
+    lock: &'w FairRwLock<T>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for FairRwLockWriteGuard<'_, T> {}
+
+impl<T: ?Sized> Deref for FairRwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The write lock is held, giving us exclusive access.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for FairRwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The write lock is held, giving us exclusive access.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for FairRwLockWriteGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // Let the readers that arrived during this phase in, then pass the
+        // turn to the next writer.
+        self.lock
+            .reader_in
+            .fetch_and(!WRITER_BITS, Ordering::Release);
+        self.lock.writer_out.fetch_add(1, Ordering::Release);
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for FairRwLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for FairRwLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
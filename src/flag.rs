@@ -0,0 +1,71 @@
+use crate::mutex::{Mutex, MutexGuard};
+use core::fmt::{Debug, Formatter};
+
+/// A lock that protects no data, used purely for mutual exclusion.
+///
+/// This is a [`Mutex<()>`] with constructors and accessors named for what it
+/// is used for: guarding an external resource, such as a bus or a log sink,
+/// for as long as a token is held. It is a single byte under every feature
+/// set, like the mutex it wraps.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::Flag;
+///
+/// static BUS: Flag = Flag::new();
+///
+/// let token = BUS.acquire_token();
+/// assert!(BUS.try_acquire_token().is_none());
+///
+/// drop(token);
+/// assert!(BUS.try_acquire_token().is_some());
+/// ```
+pub struct Flag {
+    mutex: Mutex<()>,
+}
+
+const _: () = assert!(size_of::<Flag>() == 1);
+
+impl Flag {
+    /// Creates a new flag that is not held.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            mutex: Mutex::new(()),
+        }
+    }
+
+    /// Acquires the flag, blocking the current thread until it is able to
+    /// do so. The flag is released when the token is dropped.
+    pub fn acquire_token(&self) -> MutexGuard<'_, ()> {
+        self.mutex.lock()
+    }
+
+    /// Attempts to acquire the flag without blocking.
+    #[must_use]
+    pub fn try_acquire_token(&self) -> Option<MutexGuard<'_, ()>> {
+        self.mutex.try_lock()
+    }
+
+    /// Returns the underlying mutex.
+    #[inline]
+    #[must_use]
+    pub const fn as_mutex(&self) -> &Mutex<()> {
+        &self.mutex
+    }
+}
+
+impl Default for Flag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Flag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Flag")
+            .field("held", &self.mutex.try_lock().is_none())
+            .finish()
+    }
+}
@@ -0,0 +1,158 @@
+use crate::once_lock::OnceLock;
+use core::fmt::{Debug, Display, Formatter};
+use core::panic::Location;
+
+/// A slot for installing a global singleton exactly once.
+///
+/// This is meant for process-wide handles such as an allocator, a logger or
+/// a panic reporter: [`install`] succeeds only the first time, remembering
+/// where it was called from, and later reads are a single atomic load.
+/// Installing twice or reading before installing is reported with the name
+/// of the slot and the location of the first installation.
+///
+/// [`install`]: Self::install
+///
+/// # Examples
+/// ```
+/// use skirt::sync::GlobalSlot;
+///
+/// static LOGGER: GlobalSlot<&str> = GlobalSlot::new("logger");
+///
+/// assert!(LOGGER.get().is_none());
+/// LOGGER.install("serial").unwrap();
+/// assert_eq!(*LOGGER.expect_installed(), "serial");
+///
+/// let error = LOGGER.install("usb").unwrap_err();
+/// assert_eq!(error.into_inner(), "usb");
+/// ```
+pub struct GlobalSlot<T> {
+    name: &'static str,
+    cell: OnceLock<Installed<T>>,
+}
+
+struct Installed<T> {
+    value: T,
+    location: &'static Location<'static>,
+}
+
+impl<T> GlobalSlot<T> {
+    /// Creates a new, empty slot, named `name` in diagnostics.
+    #[inline]
+    #[must_use]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            cell: OnceLock::new(),
+        }
+    }
+
+    /// Installs `value` into the slot, returning a reference to it.
+    ///
+    /// # Errors
+    /// Returns an [`InstallError`] holding `value` if the slot was already
+    /// installed.
+    #[track_caller]
+    pub fn install(&self, value: T) -> Result<&T, InstallError<T>> {
+        let location = Location::caller();
+
+        match self.cell.set(Installed { value, location }) {
+            Ok(()) => Ok(self.expect_installed()),
+            Err(Installed { value, .. }) => Err(InstallError {
+                value,
+                name: self.name,
+                first: self.installed_at(),
+            }),
+        }
+    }
+
+    /// Returns the installed value, or [`None`] if nothing was installed yet.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get().map(|installed| &installed.value)
+    }
+
+    /// Returns the installed value.
+    ///
+    /// # Panics
+    /// Panics if nothing was installed yet.
+    #[inline]
+    #[track_caller]
+    pub fn expect_installed(&self) -> &T {
+        let Some(value) = self.get() else {
+            panic!("global `{}` used before it was installed", self.name);
+        };
+
+        value
+    }
+
+    /// Returns where the value was installed from, or [`None`] if nothing
+    /// was installed yet.
+    pub fn installed_at(&self) -> Option<&'static Location<'static>> {
+        self.cell.get().map(|installed| installed.location)
+    }
+
+    /// Returns the name of this slot.
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T: Debug> Debug for GlobalSlot<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("GlobalSlot");
+        d.field("name", &self.name);
+
+        match self.get() {
+            Some(value) => d.field("value", value),
+            None => d.field("value", &format_args!("<uninit>")),
+        };
+
+        d.finish_non_exhaustive()
+    }
+}
+
+/// The error returned by [`GlobalSlot::install`] when the slot was already
+/// installed.
+pub struct InstallError<T> {
+    value: T,
+    name: &'static str,
+    first: Option<&'static Location<'static>>,
+}
+
+impl<T> InstallError<T> {
+    /// Returns the value that could not be installed.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns where the slot was first installed from.
+    #[must_use]
+    pub const fn first_installed_at(&self) -> Option<&'static Location<'static>> {
+        self.first
+    }
+}
+
+impl<T> Debug for InstallError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InstallError")
+            .field("name", &self.name)
+            .field("first", &self.first)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Display for InstallError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "global `{}` is already installed", self.name)?;
+
+        if let Some(location) = self.first {
+            write!(f, " (first installed at {location})")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> core::error::Error for InstallError<T> {}
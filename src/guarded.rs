@@ -0,0 +1,161 @@
+use crate::mutex::{Mutex, MutexGuard};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+
+/// A tag type naming exactly one [`Mutex`] that protects [`Guarded`] data.
+///
+/// This trait is usually implemented through the [`guarded!`] macro, which
+/// declares the tag type, its mutex, and the association between them.
+///
+/// [`guarded!`]: crate::guarded!
+pub trait LockTag: Sized + 'static {
+    /// Returns the mutex identified by this tag.
+    fn mutex() -> &'static Mutex<Self>;
+}
+
+/// Data that can only be accessed while holding the lock named by `Tag`.
+///
+/// Unlike a [`Mutex`], a `Guarded` value has no lock of its own. Instead, any
+/// number of `Guarded` values can be declared as protected by a single named
+/// lock, and are reached through a guard of that lock. Touching the data
+/// without holding the right lock is a compile-time error, and passing a guard
+/// of a different mutex with the same tag type panics.
+///
+/// # Examples
+/// ```
+/// use skirt::guarded;
+/// use skirt::sync::Guarded;
+///
+/// guarded! {
+///     static STATE_LOCK: StateLock;
+/// }
+///
+/// static COUNT: Guarded<u32, StateLock> = Guarded::new(0);
+/// static NAME: Guarded<&str, StateLock> = Guarded::new("idle");
+///
+/// let mut guard = STATE_LOCK.lock();
+/// *COUNT.get_mut(&mut guard) += 1;
+/// *NAME.get_mut(&mut guard) = "busy";
+///
+/// assert_eq!(*COUNT.get(&guard), 1);
+/// assert_eq!(*NAME.get(&guard), "busy");
+/// ```
+pub struct Guarded<T: ?Sized, Tag: LockTag> {
+    phantom: PhantomData<fn() -> Tag>,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send, Tag: LockTag> Send for Guarded<T, Tag> {}
+unsafe impl<T: ?Sized + Send + Sync, Tag: LockTag> Sync for Guarded<T, Tag> {}
+
+impl<T, Tag: LockTag> Guarded<T, Tag> {
+    /// Creates a new value protected by the lock named by `Tag`.
+    #[inline]
+    pub const fn new(data: T) -> Self {
+        Self {
+            phantom: PhantomData,
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consumes this value, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized, Tag: LockTag> Guarded<T, Tag> {
+    /// Returns a shared reference to the data, borrowed from a guard of the
+    /// lock named by `Tag`.
+    ///
+    /// # Panics
+    /// Panics if `guard` does not belong to the mutex returned by
+    /// [`LockTag::mutex`].
+    pub fn get<'g>(&'g self, guard: &'g MutexGuard<'_, Tag>) -> &'g T {
+        Self::check(guard);
+        // SAFETY: The named lock is held and borrowed for `'g`.
+        unsafe { &*self.data.get() }
+    }
+
+    /// Returns a mutable reference to the data, borrowed from a guard of the
+    /// lock named by `Tag`.
+    ///
+    /// # Panics
+    /// Panics if `guard` does not belong to the mutex returned by
+    /// [`LockTag::mutex`].
+    pub fn get_mut<'g>(&'g self, guard: &'g mut MutexGuard<'_, Tag>) -> &'g mut T {
+        Self::check(guard);
+        // SAFETY: The named lock is held and mutably borrowed for `'g`.
+        unsafe { &mut *self.data.get() }
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `Guarded` mutably, no locking needs to take
+    /// place.
+    pub const fn get_mut_unguarded(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    #[inline]
+    fn check(guard: &MutexGuard<'_, Tag>) {
+        assert!(
+            core::ptr::eq(guard.mutex, Tag::mutex()),
+            "guard does not belong to the lock protecting this data"
+        );
+    }
+}
+
+impl<T: Default, Tag: LockTag> Default for Guarded<T, Tag> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: ?Sized, Tag: LockTag> Debug for Guarded<T, Tag> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Guarded").finish_non_exhaustive()
+    }
+}
+
+/// Declares a named lock that can protect [`Guarded`] data.
+///
+/// This expands to a tag type, a `static` [`Mutex`] of that tag, and the
+/// [`LockTag`] implementation tying the two together.
+///
+/// [`Guarded`]: crate::sync::Guarded
+/// [`Mutex`]: crate::sync::Mutex
+/// [`LockTag`]: crate::sync::LockTag
+///
+/// # Examples
+/// ```
+/// use skirt::guarded;
+/// use skirt::sync::Guarded;
+///
+/// guarded! {
+///     /// Protects the device registry.
+///     pub static REGISTRY_LOCK: RegistryLock;
+/// }
+///
+/// static DEVICES: Guarded<[u8; 4], RegistryLock> = Guarded::new([0; 4]);
+///
+/// DEVICES.get_mut(&mut REGISTRY_LOCK.lock())[0] = 1;
+/// ```
+#[macro_export]
+macro_rules! guarded {
+    ($(#[$meta:meta])* $vis:vis static $name:ident: $tag:ident;) => {
+        $(#[$meta])*
+        $vis static $name: $crate::sync::Mutex<$tag> = $crate::sync::Mutex::new($tag(()));
+
+        #[doc = concat!("Tag type of [`", stringify!($name), "`].")]
+        #[derive(Debug)]
+        $vis struct $tag(());
+
+        impl $crate::sync::LockTag for $tag {
+            fn mutex() -> &'static $crate::sync::Mutex<Self> {
+                &$name
+            }
+        }
+    };
+}
@@ -0,0 +1,106 @@
+use crate::mutex::Mutex;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use core::fmt::{Debug, Formatter};
+use core::num::NonZeroUsize;
+
+/// An allocator of unique, non-zero identifiers.
+///
+/// Identifiers are handed out from an atomic counter starting at `1`. Freed
+/// identifiers are kept on a free-list of up to `N` entries, protected by a
+/// [`Mutex`], and handed out again before the counter advances. With the
+/// default `N = 0` there is no free-list and allocation never locks.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::IdAllocator;
+///
+/// static IDS: IdAllocator<4> = IdAllocator::new();
+///
+/// let a = IDS.alloc().unwrap();
+/// let b = IDS.alloc().unwrap();
+/// assert_ne!(a, b);
+///
+/// assert!(IDS.free(a));
+/// assert_eq!(IDS.alloc(), Some(a));
+/// ```
+pub struct IdAllocator<const N: usize = 0> {
+    next: AtomicUsize,
+    free: Mutex<FreeList<N>>,
+}
+
+struct FreeList<const N: usize> {
+    ids: [usize; N],
+    len: usize,
+}
+
+impl<const N: usize> IdAllocator<N> {
+    /// Creates a new allocator whose first identifier is `1`.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicUsize::new(1),
+            free: Mutex::new(FreeList {
+                ids: [0; N],
+                len: 0,
+            }),
+        }
+    }
+
+    /// Allocates a unique identifier, or returns [`None`] if every identifier
+    /// has been handed out and none were freed.
+    pub fn alloc(&self) -> Option<NonZeroUsize> {
+        if N > 0 {
+            let mut free = self.free.lock();
+
+            if free.len > 0 {
+                free.len -= 1;
+                return NonZeroUsize::new(free.ids[free.len]);
+            }
+        }
+
+        self.next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |next| {
+                (next != 0).then(|| next.wrapping_add(1))
+            })
+            .ok()
+            .and_then(NonZeroUsize::new)
+    }
+
+    /// Returns `id` to the allocator so it can be handed out again.
+    ///
+    /// Returns `false` if the free-list is full, in which case `id` is retired
+    /// and never reused. The caller must not free an identifier that is still
+    /// in use or free the same identifier twice.
+    pub fn free(&self, id: NonZeroUsize) -> bool {
+        if N == 0 {
+            return false;
+        }
+
+        let mut free = self.free.lock();
+
+        if free.len == N {
+            return false;
+        }
+
+        let len = free.len;
+        free.ids[len] = id.get();
+        free.len += 1;
+
+        true
+    }
+}
+
+impl<const N: usize> Default for IdAllocator<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Debug for IdAllocator<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IdAllocator")
+            .field("next", &self.next.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
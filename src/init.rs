@@ -0,0 +1,133 @@
+//! Eager initialization of lazily initialized statics.
+//!
+//! Lazy statics pay their initialization cost on first use, which may happen
+//! somewhere it hurts, such as in an interrupt handler. Statics registered
+//! with [`register_init!`] can instead be initialized up front, in
+//! registration order, by a single [`run_all`] call at startup.
+//!
+//! [`register_init!`]: crate::register_init!
+//!
+//! # Examples
+//! ```
+//! use skirt::register_init;
+//! use skirt::sync::LazyLock;
+//!
+//! static TABLE: LazyLock<[u32; 4]> = LazyLock::new(|| core::array::from_fn(|i| 1 << i));
+//! static SUM: LazyLock<u32> = LazyLock::new(|| TABLE.iter().sum());
+//!
+//! register_init!(TABLE, SUM);
+//! skirt::init::run_all();
+//!
+//! assert_eq!(LazyLock::get(&SUM), Some(&15));
+//! ```
+
+use crate::lazy_lock::LazyLock;
+use crate::mutex::Mutex;
+use crate::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A value that can be initialized ahead of its first use.
+pub trait EagerInit: Sync {
+    /// Initializes the value if it has not been initialized yet.
+    fn eager_init(&self);
+}
+
+impl<T: Sync + Send, F: FnOnce() -> T + Send> EagerInit for LazyLock<T, F> {
+    fn eager_init(&self) {
+        Self::force(self);
+    }
+}
+
+/// A node in the initialization registry.
+///
+/// Entries are usually declared by [`register_init!`](crate::register_init!)
+/// rather than by hand.
+pub struct Entry {
+    target: &'static dyn EagerInit,
+    registered: AtomicBool,
+    next: AtomicPtr<Self>,
+}
+
+impl Entry {
+    /// Creates a new entry for `target`.
+    #[must_use]
+    pub const fn new(target: &'static dyn EagerInit) -> Self {
+        Self {
+            target,
+            registered: AtomicBool::new(false),
+            next: AtomicPtr::new(core::ptr::null_mut()),
+        }
+    }
+}
+
+impl Debug for Entry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Entry")
+            .field("registered", &self.registered.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+struct Registry {
+    head: Option<&'static Entry>,
+    tail: Option<&'static Entry>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    head: None,
+    tail: None,
+});
+
+/// Appends `entry` to the registry, returning `false` if it was already
+/// registered.
+pub fn register(entry: &'static Entry) -> bool {
+    if entry.registered.swap(true, Ordering::AcqRel) {
+        return false;
+    }
+
+    let mut registry = REGISTRY.lock();
+
+    match registry.tail {
+        Some(tail) => tail
+            .next
+            .store(core::ptr::from_ref(entry).cast_mut(), Ordering::Release),
+        None => registry.head = Some(entry),
+    }
+
+    registry.tail = Some(entry);
+    true
+}
+
+/// Initializes every registered value, in registration order.
+///
+/// Values that are already initialized are skipped, so this is cheap to call
+/// more than once. The registry is not locked while initializers run, so an
+/// initializer may register further entries, which are then also run.
+pub fn run_all() {
+    let mut entry = REGISTRY.lock().head;
+
+    while let Some(current) = entry {
+        current.target.eager_init();
+
+        // SAFETY: `next` only ever points to a registered `'static` entry.
+        entry = unsafe { current.next.load(Ordering::Acquire).as_ref() };
+    }
+}
+
+/// Registers lazily initialized statics to be initialized by
+/// [`init::run_all`](crate::init::run_all).
+///
+/// Each argument must be a `static` implementing
+/// [`EagerInit`](crate::init::EagerInit), such as a
+/// [`LazyLock`](crate::sync::LazyLock). The statics are initialized in the
+/// order they are registered, and registering the same callsite twice has no
+/// effect.
+#[macro_export]
+macro_rules! register_init {
+    ($($target:path),+ $(,)?) => {{
+        $({
+            static ENTRY: $crate::init::Entry = $crate::init::Entry::new(&$target);
+            $crate::init::register(&ENTRY);
+        })+
+    }};
+}
@@ -0,0 +1,107 @@
+use crate::once::Once;
+use crate::profile::Backoff;
+use core::fmt::{Debug, Formatter};
+
+/// A flag signalling that something has been initialized, safe to use from
+/// interrupt handlers.
+///
+/// Thread code can run an initialization routine exactly once with
+/// [`init`], like [`Once::call_once`]. Interrupt handlers can check the flag
+/// with [`is_set`] and raise it with [`set_from_isr`], neither of which ever
+/// spins or blocks.
+///
+/// [`init`]: Self::init
+/// [`is_set`]: Self::is_set
+/// [`set_from_isr`]: Self::set_from_isr
+///
+/// # Examples
+/// ```
+/// use skirt::sync::InitFlag;
+///
+/// static DRIVER_READY: InitFlag = InitFlag::new();
+///
+/// // In the interrupt handler:
+/// assert!(DRIVER_READY.set_from_isr());
+///
+/// // In thread code:
+/// assert!(DRIVER_READY.is_set());
+/// DRIVER_READY.init(|| unreachable!("already initialized"));
+/// ```
+pub struct InitFlag {
+    once: Once,
+}
+
+impl InitFlag {
+    /// A flag that is not set, usable where a constant is needed, such as in
+    /// array repeat expressions.
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const INIT: Self = Self::new();
+
+    /// Creates a new flag that is not set.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { once: Once::new() }
+    }
+
+    /// Returns whether the flag is set.
+    ///
+    /// This is a single atomic load and is safe to call from interrupt
+    /// context.
+    #[inline]
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.once.is_completed()
+    }
+
+    /// Sets the flag without running any routine, returning whether this
+    /// call set it.
+    ///
+    /// This never spins. It returns `false` if the flag was already set, or
+    /// if thread code is in the middle of [`init`](Self::init), in which case
+    /// the flag will be set once that routine finishes.
+    pub fn set_from_isr(&self) -> bool {
+        if !self.once.try_begin() {
+            return false;
+        }
+
+        self.once.finish();
+        true
+    }
+
+    /// Runs `f` and sets the flag, unless the flag has already been set.
+    ///
+    /// If another thread is running its routine, this blocks until it
+    /// finishes. This must not be called from interrupt context.
+    pub fn init<F>(&self, f: F)
+    where
+        F: FnOnce(),
+    {
+        self.once.call_once(f);
+    }
+
+    /// Blocks until the flag is set.
+    ///
+    /// This must not be called from interrupt context.
+    pub fn wait(&self) {
+        let mut backoff = Backoff::new();
+
+        while !self.is_set() {
+            backoff.relax();
+        }
+    }
+}
+
+impl Default for InitFlag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for InitFlag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InitFlag")
+            .field("set", &self.is_set())
+            .finish()
+    }
+}
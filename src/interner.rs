@@ -0,0 +1,109 @@
+use crate::rwlock::RwLock;
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use core::fmt::{Debug, Formatter};
+
+/// A set of interned strings, each stored once for the rest of the program.
+///
+/// [`intern`] returns a `&'static str` equal to its argument, and every call
+/// with an equal string returns the same reference, so interned strings can
+/// be compared by pointer and stored without lifetimes. Interned strings are
+/// leaked and never freed, which suits bounded sets such as identifiers,
+/// metric names or configuration keys.
+///
+/// Lookups of strings that are already interned only take a read lock.
+///
+/// [`intern`]: Self::intern
+///
+/// # Examples
+/// ```
+/// use skirt::sync::Interner;
+///
+/// static NAMES: Interner = Interner::new();
+///
+/// let a = NAMES.intern("sensor");
+/// let b = NAMES.intern(&String::from("sensor"));
+///
+/// assert!(core::ptr::eq(a, b));
+/// assert_eq!(NAMES.len(), 1);
+/// ```
+pub struct Interner {
+    strings: RwLock<BTreeSet<&'static str>>,
+}
+
+impl Interner {
+    /// Creates a new, empty interner.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            strings: RwLock::new(BTreeSet::new()),
+        }
+    }
+
+    /// Returns a process-wide interner.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Interner;
+    ///
+    /// let name = Interner::global().intern("uart0");
+    /// assert_eq!(Interner::global().get("uart0"), Some(name));
+    /// ```
+    #[must_use]
+    pub fn global() -> &'static Self {
+        static GLOBAL: Interner = Interner::new();
+        &GLOBAL
+    }
+
+    /// Returns the interned copy of `string`, interning it first if needed.
+    pub fn intern(&self, string: &str) -> &'static str {
+        if let Some(interned) = self.get(string) {
+            return interned;
+        }
+
+        let mut strings = self.strings.write();
+
+        // Another thread may have interned the string in the meantime.
+        if let Some(&interned) = strings.get(string) {
+            return interned;
+        }
+
+        let interned: &'static str = Box::leak(Box::from(string));
+        strings.insert(interned);
+        interned
+    }
+
+    /// Returns the interned copy of `string`, or [`None`] if it has not been
+    /// interned.
+    #[must_use]
+    pub fn get(&self, string: &str) -> Option<&'static str> {
+        self.strings.read().get(string).copied()
+    }
+
+    /// Returns the number of interned strings.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.read().len()
+    }
+
+    /// Returns whether no string has been interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.read().is_empty()
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Interner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Interner")
+            .field("strings", &self.strings)
+            .finish()
+    }
+}
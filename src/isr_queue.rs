@@ -0,0 +1,251 @@
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Formatter};
+use core::mem::MaybeUninit;
+
+/// What [`IsrQueue::push_from_isr`] does when the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// Reject the new event, handing it back to the caller.
+    Reject,
+    /// Drop the oldest queued event to make room for the new one.
+    DropOldest,
+}
+
+/// A bounded queue of events produced by interrupt handlers and consumed by
+/// thread code.
+///
+/// Any number of interrupt handlers or threads may [`push_from_isr`], which
+/// never takes a lock. Both ends are lock-free rather than wait-free: a push
+/// or pop retries while others racing with it succeed. When the queue is
+/// full, the
+/// [`OverflowPolicy`] given at construction decides whether the new event is
+/// rejected or the oldest one is dropped. Thread code takes events out with
+/// [`pop`].
+///
+/// [`push_from_isr`]: Self::push_from_isr
+/// [`pop`]: Self::pop
+///
+/// # Examples
+/// ```
+/// use skirt::sync::{IsrQueue, OverflowPolicy};
+///
+/// static EVENTS: IsrQueue<u8, 2> = IsrQueue::new(OverflowPolicy::DropOldest);
+///
+/// // In the interrupt handler:
+/// EVENTS.push_from_isr(1).unwrap();
+/// EVENTS.push_from_isr(2).unwrap();
+/// EVENTS.push_from_isr(3).unwrap();
+///
+/// // In thread code:
+/// assert_eq!(EVENTS.pop(), Some(2));
+/// assert_eq!(EVENTS.pop(), Some(3));
+/// assert_eq!(EVENTS.pop(), None);
+/// ```
+pub struct IsrQueue<T, const N: usize> {
+    slots: [Slot<T>; N],
+    enqueue: AtomicUsize,
+    dequeue: AtomicUsize,
+    policy: OverflowPolicy,
+}
+
+// Each slot carries a sequence number telling which lap of the ring it is
+// ready for: a slot at index `i` may be written at position `pos` when its
+// sequence is `pos`, and read at `pos` when it is `pos + 1`. To allow a const
+// constructor, the stored value is offset by `i`, so all slots start at zero.
+struct Slot<T> {
+    sequence: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send, const N: usize> Send for IsrQueue<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for IsrQueue<T, N> {}
+
+impl<T, const N: usize> IsrQueue<T, N> {
+    /// Creates a new, empty queue with the given overflow policy.
+    ///
+    /// # Panics
+    /// Panics if `N` is not a power of two. Positions in the ring wrap
+    /// around `usize::MAX`, which only lands on the same slot for such `N`.
+    #[inline]
+    #[must_use]
+    pub const fn new(policy: OverflowPolicy) -> Self {
+        assert!(
+            N.is_power_of_two(),
+            "an IsrQueue needs a power of two slots"
+        );
+
+        Self {
+            slots: [const {
+                Slot {
+                    sequence: AtomicUsize::new(0),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                }
+            }; N],
+            enqueue: AtomicUsize::new(0),
+            dequeue: AtomicUsize::new(0),
+            policy,
+        }
+    }
+
+    /// Pushes an event onto the queue without taking a lock.
+    ///
+    /// This is safe to call from interrupt context, and from several
+    /// producers at once.
+    ///
+    /// # Errors
+    /// Returns `value` back if the queue is full and the policy is
+    /// [`OverflowPolicy::Reject`].
+    ///
+    /// Under [`OverflowPolicy::DropOldest`], this drops at most one event
+    /// per push, and may still fail instead of waiting on a thread that this
+    /// interrupt may have preempted:
+    ///
+    /// - if a consumer has taken the event in the next slot but not yet
+    ///   released the slot, in which case nothing is dropped,
+    /// - if the oldest event cannot be dropped because its producer has not
+    ///   finished writing it,
+    /// - or if another producer fills the room made by dropping an event.
+    pub fn push_from_isr(&self, value: T) -> Result<(), T> {
+        let value = match self.try_push(value) {
+            Ok(()) => return Ok(()),
+            Err(value) if self.policy == OverflowPolicy::Reject => return Err(value),
+            Err(value) => value,
+        };
+
+        // The push can also fail on a slot that a consumer is still
+        // releasing, which dropping more events would not help with.
+        if self.len() < N || self.pop().is_none() {
+            return Err(value);
+        }
+
+        self.try_push(value)
+    }
+
+    /// Takes the oldest event out of the queue, or returns [`None`] if it is
+    /// empty.
+    pub fn pop(&self) -> Option<T> {
+        let mut position = self.dequeue.load(Ordering::Relaxed);
+
+        loop {
+            let (slot, sequence) = self.slot(position);
+            let lag = Self::distance(sequence, position.wrapping_add(1));
+
+            match lag {
+                0 => {
+                    match self.dequeue.compare_exchange_weak(
+                        position,
+                        position.wrapping_add(1),
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => {
+                            // SAFETY: Winning the position gives us exclusive
+                            // access to the slot, which holds a value.
+                            let value = unsafe { (*slot.value.get()).assume_init_read() };
+                            self.publish(position, position.wrapping_add(N));
+                            return Some(value);
+                        }
+                        Err(current) => position = current,
+                    }
+                }
+                ..0 => return None,
+                _ => position = self.dequeue.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Returns the number of events the queue can hold.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of queued events.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let dequeue = self.dequeue.load(Ordering::Relaxed);
+        let enqueue = self.enqueue.load(Ordering::Relaxed);
+        enqueue.wrapping_sub(dequeue).min(N)
+    }
+
+    /// Returns whether the queue is empty.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn try_push(&self, value: T) -> Result<(), T> {
+        let mut position = self.enqueue.load(Ordering::Relaxed);
+
+        loop {
+            let (slot, sequence) = self.slot(position);
+            let lag = Self::distance(sequence, position);
+
+            match lag {
+                0 => {
+                    match self.enqueue.compare_exchange_weak(
+                        position,
+                        position.wrapping_add(1),
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => {
+                            // SAFETY: Winning the position gives us exclusive
+                            // access to the slot, which is empty.
+                            unsafe { (*slot.value.get()).write(value) };
+                            self.publish(position, position.wrapping_add(1));
+                            return Ok(());
+                        }
+                        Err(current) => position = current,
+                    }
+                }
+                ..0 => return Err(value),
+                _ => position = self.enqueue.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Returns the slot for `position` and its sequence number.
+    fn slot(&self, position: usize) -> (&Slot<T>, usize) {
+        let index = position % N;
+        let slot = &self.slots[index];
+        let sequence = slot.sequence.load(Ordering::Acquire).wrapping_add(index);
+        (slot, sequence)
+    }
+
+    /// Sets the sequence number of the slot for `position`.
+    fn publish(&self, position: usize, sequence: usize) {
+        let index = position % N;
+        self.slots[index]
+            .sequence
+            .store(sequence.wrapping_sub(index), Ordering::Release);
+    }
+
+    /// Returns how far `sequence` is ahead of `expected`, accounting for
+    /// wrap-around.
+    #[allow(clippy::cast_possible_wrap)]
+    const fn distance(sequence: usize, expected: usize) -> isize {
+        sequence.wrapping_sub(expected) as isize
+    }
+}
+
+impl<T, const N: usize> Drop for IsrQueue<T, N> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+impl<T, const N: usize> Debug for IsrQueue<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IsrQueue")
+            .field("len", &self.len())
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
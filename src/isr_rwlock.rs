@@ -0,0 +1,190 @@
+use crate::mutex::Mutex;
+use crate::sync::atomic::{AtomicUsize, Ordering, fence};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Formatter};
+use core::mem::MaybeUninit;
+
+/// A reader-writer cell whose readers never block, for data read from
+/// interrupt handlers and written by thread code.
+///
+/// The value is kept in two copies, and a sequence number tells readers
+/// which copy is stable. A writer updates one copy at a time, steering
+/// readers to the other one, so a [`read`] that interrupts a half-finished
+/// [`write`] returns the previous value instead of waiting for a writer that
+/// cannot run until the interrupt handler returns. A reader only retries if
+/// a writer on another core publishes while it is copying the value.
+///
+/// Writers are serialized with a spinlock, and must not be called from
+/// interrupt context.
+///
+/// Reads return a copy of the value, so `T` must be [`Copy`].
+///
+/// [`read`]: Self::read
+/// [`write`]: Self::write
+///
+/// # Examples
+/// ```
+/// use skirt::sync::IsrRwLock;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Config {
+///     threshold: u16,
+///     enabled: bool,
+/// }
+///
+/// static CONFIG: IsrRwLock<Config> = IsrRwLock::new(Config { threshold: 10, enabled: false });
+///
+/// // In the main loop:
+/// CONFIG.update(|config| config.enabled = true);
+///
+/// // In the interrupt handler:
+/// assert_eq!(CONFIG.read(), Config { threshold: 10, enabled: true });
+/// ```
+pub struct IsrRwLock<T> {
+    sequence: AtomicUsize,
+    writer: Mutex<()>,
+    copies: [UnsafeCell<T>; 2],
+}
+
+unsafe impl<T: Send> Send for IsrRwLock<T> {}
+unsafe impl<T: Send> Sync for IsrRwLock<T> {}
+
+impl<T: Copy> IsrRwLock<T> {
+    /// Creates a new cell holding `data`.
+    #[inline]
+    pub const fn new(data: T) -> Self {
+        Self {
+            sequence: AtomicUsize::new(0),
+            writer: Mutex::new(()),
+            copies: [UnsafeCell::new(data), UnsafeCell::new(data)],
+        }
+    }
+
+    /// Returns a copy of the current value.
+    ///
+    /// This never waits on a writer and is safe to call from interrupt
+    /// context.
+    pub fn read(&self) -> T {
+        loop {
+            let sequence = self.sequence.load(Ordering::Acquire);
+
+            // SAFETY: The copy may be written concurrently by a writer on
+            // another core, so it is read as possibly torn bytes, and only
+            // assumed to be a valid `T` once the sequence shows that no
+            // writer touched it meanwhile.
+            let value = unsafe {
+                self.copies[sequence & 1]
+                    .get()
+                    .cast::<MaybeUninit<T>>()
+                    .read_volatile()
+            };
+
+            fence(Ordering::Acquire);
+
+            if self.sequence.load(Ordering::Relaxed) == sequence {
+                // SAFETY: No writer touched the copy while it was read.
+                return unsafe { value.assume_init() };
+            }
+        }
+    }
+
+    /// Replaces the value with `data`, waiting for other writers.
+    ///
+    /// This must not be called from interrupt context.
+    pub fn write(&self, data: T) {
+        let _writer = self.writer.lock();
+        self.publish(data);
+    }
+
+    /// Replaces the value with `data` if no other writer is active.
+    ///
+    /// # Errors
+    /// Returns `data` back if another writer holds the lock.
+    pub fn try_write(&self, data: T) -> Result<(), T> {
+        let Some(_writer) = self.writer.try_lock() else {
+            return Err(data);
+        };
+
+        self.publish(data);
+        Ok(())
+    }
+
+    /// Modifies a copy of the value with `f` and publishes it, waiting for
+    /// other writers.
+    ///
+    /// Readers keep seeing the previous value until `f` returns. If `f`
+    /// panics, nothing is published.
+    ///
+    /// This must not be called from interrupt context.
+    pub fn update<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let _writer = self.writer.lock();
+
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        // SAFETY: Only writers modify the copies, and the writer lock is
+        // held.
+        let mut data = unsafe { *self.copies[sequence & 1].get() };
+        let result = f(&mut data);
+        self.publish(data);
+
+        result
+    }
+
+    /// Returns a mutable reference to the current value.
+    ///
+    /// Since this call borrows the `IsrRwLock` mutably, no actual locking
+    /// needs to take place.
+    pub fn get_mut(&mut self) -> &mut T {
+        // The other copy is only read again after the next write updates it.
+        let stable = self.sequence.load(Ordering::Relaxed) & 1;
+        self.copies[stable].get_mut()
+    }
+
+    /// Consumes this cell, returning the current value.
+    pub fn into_inner(self) -> T {
+        let stable = self.sequence.load(Ordering::Relaxed) & 1;
+        let [first, second] = self.copies;
+
+        if stable == 0 {
+            first.into_inner()
+        } else {
+            second.into_inner()
+        }
+    }
+
+    /// Writes `data` to both copies in turn, steering readers away from the
+    /// copy being written. The writer lock must be held.
+    fn publish(&self, data: T) {
+        for _ in 0..2 {
+            // Steer readers to the copy written last, publishing it, and
+            // make readers of the other copy retry.
+            let sequence = self.sequence.load(Ordering::Relaxed).wrapping_add(1);
+            self.sequence.store(sequence, Ordering::Release);
+            fence(Ordering::Release);
+
+            // SAFETY: Readers now read the other copy, and retry if they
+            // were reading this one. The writer lock excludes other writers.
+            unsafe { self.copies[(sequence & 1) ^ 1].get().write_volatile(data) };
+        }
+    }
+}
+
+impl<T: Copy + Default> Default for IsrRwLock<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Copy> From<T> for IsrRwLock<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: Copy + Debug> Debug for IsrRwLock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("IsrRwLock").field(&self.read()).finish()
+    }
+}
@@ -0,0 +1,112 @@
+use crate::once::Once;
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Formatter};
+use core::mem::ManuallyDrop;
+
+// Like `LazyLock`, the state of the `Once` is the discriminant: `f` is live
+// until the first `force` completes, `value` afterwards.
+union Data<T, F> {
+    value: ManuallyDrop<T>,
+    f: ManuallyDrop<F>,
+}
+
+/// A value which is initialized on first access from an argument given to
+/// that access.
+///
+/// The first call to [`force`] passes its argument to the initializing
+/// function, and later calls ignore theirs. This covers initialization that
+/// needs context only the first caller has at hand, such as the peripheral
+/// it happens to own.
+///
+/// [`force`]: Self::force
+///
+/// # Examples
+/// ```
+/// use skirt::sync::LazyWith;
+///
+/// static GREETING: LazyWith<&str, String> = LazyWith::new(|name| format!("hello, {name}"));
+///
+/// assert_eq!(LazyWith::get(&GREETING), None);
+/// assert_eq!(LazyWith::force(&GREETING, "first"), "hello, first");
+/// assert_eq!(LazyWith::force(&GREETING, "second"), "hello, first");
+/// ```
+pub struct LazyWith<A, T, F = fn(A) -> T> {
+    once: Once,
+    data: UnsafeCell<Data<T, F>>,
+    phantom: core::marker::PhantomData<fn(A)>,
+}
+
+unsafe impl<A, T: Sync + Send, F: Send> Sync for LazyWith<A, T, F> {}
+
+impl<A, T, F: FnOnce(A) -> T> LazyWith<A, T, F> {
+    /// Creates a new value with the given initializing function.
+    #[inline]
+    pub const fn new(f: F) -> Self {
+        Self {
+            once: Once::new(),
+            data: UnsafeCell::new(Data {
+                f: ManuallyDrop::new(f),
+            }),
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Forces the evaluation of this value with `arg`, returning a reference
+    /// to it.
+    ///
+    /// If the value is already initialized, `arg` is dropped. If another
+    /// thread is currently initializing it, this blocks until it is done.
+    #[inline]
+    pub fn force(this: &Self, arg: A) -> &T {
+        this.once.call_once(|| {
+            // SAFETY: `call_once` only runs this closure once, ever.
+            let data = unsafe { &mut *this.data.get() };
+            let f = unsafe { ManuallyDrop::take(&mut data.f) };
+            let value = f(arg);
+            data.value = ManuallyDrop::new(value);
+        });
+
+        unsafe { &(*this.data.get()).value }
+    }
+}
+
+impl<A, T, F> LazyWith<A, T, F> {
+    /// Returns the value if it has been initialized.
+    #[inline]
+    pub fn get(this: &Self) -> Option<&T> {
+        if this.once.is_completed() {
+            Some(unsafe { &(*this.data.get()).value })
+        } else {
+            None
+        }
+    }
+}
+
+impl<A, T, F> Drop for LazyWith<A, T, F> {
+    fn drop(&mut self) {
+        let data = self.data.get_mut();
+
+        // SAFETY: The state of the `Once` tells which field is live. If the
+        // initializing function panicked, it was consumed and neither is.
+        unsafe {
+            if self.once.is_completed() {
+                ManuallyDrop::drop(&mut data.value);
+            } else if self.once.try_begin() {
+                ManuallyDrop::drop(&mut data.f);
+            }
+        }
+    }
+}
+
+impl<A, T: Debug, F> Debug for LazyWith<A, T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_tuple("LazyWith");
+
+        match Self::get(self) {
+            Some(v) => d.field(v),
+            None => d.field(&format_args!("<uninit>")),
+        };
+
+        d.finish()
+    }
+}
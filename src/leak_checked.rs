@@ -0,0 +1,235 @@
+use crate::mutex::{Mutex, MutexGuard};
+use crate::sync::atomic::{AtomicPtr, Ordering};
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+use core::panic::Location;
+use core::ptr;
+
+/// A [`Mutex`] that checks its guards are released, for use in tests.
+///
+/// The mutex remembers where its current guard was acquired. Dropping the
+/// mutex while a guard is outstanding, which can only happen if the guard
+/// was leaked with [`mem::forget`] or similar, panics with that location, as
+/// does calling [`checkpoint`] while a guard is held. This helps enforce
+/// locking discipline in integration tests, such as "no lock is held across
+/// this call".
+///
+/// With the `std` feature, the checks are skipped if the thread is already
+/// panicking.
+///
+/// [`mem::forget`]: core::mem::forget
+/// [`checkpoint`]: Self::checkpoint
+///
+/// # Examples
+/// ```
+/// use skirt::sync::LeakCheckedMutex;
+///
+/// let mutex = LeakCheckedMutex::new(0);
+///
+/// *mutex.lock() += 1;
+/// mutex.checkpoint();
+/// ```
+///
+/// ```should_panic
+/// use skirt::sync::LeakCheckedMutex;
+///
+/// let mutex = LeakCheckedMutex::new(0);
+/// core::mem::forget(mutex.lock());
+///
+/// // Panics, reporting where the leaked guard was acquired.
+/// drop(mutex);
+/// ```
+pub struct LeakCheckedMutex<T: ?Sized> {
+    held_at: AtomicPtr<Location<'static>>,
+    mutex: Mutex<T>,
+}
+
+impl<T> LeakCheckedMutex<T> {
+    /// Creates a new mutex in an unlocked state ready for use.
+    #[inline]
+    pub const fn new(data: T) -> Self {
+        Self {
+            held_at: AtomicPtr::new(ptr::null_mut()),
+            mutex: Mutex::new(data),
+        }
+    }
+
+    /// Consumes this mutex, returning the underlying data.
+    ///
+    /// # Panics
+    /// Panics if a guard was leaked.
+    #[track_caller]
+    pub fn into_inner(self) -> T {
+        self.check_leaks();
+
+        // The check has passed, so nothing is left for `Drop` to do.
+        let this = core::mem::ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used or dropped again, and the remaining
+        // field has no drop glue.
+        unsafe { ptr::read(&raw const this.mutex) }.into_inner()
+    }
+}
+
+impl<T: ?Sized> LeakCheckedMutex<T> {
+    /// Acquires the mutex, blocking the current thread until it is able to
+    /// do so, and records the caller's location.
+    #[track_caller]
+    pub fn lock(&self) -> LeakCheckedGuard<'_, T> {
+        LeakCheckedGuard::new(self.mutex.lock(), &self.held_at)
+    }
+
+    /// Attempts to acquire the mutex without blocking, recording the caller's
+    /// location if it succeeds.
+    #[must_use]
+    #[track_caller]
+    pub fn try_lock(&self) -> Option<LeakCheckedGuard<'_, T>> {
+        let guard = self.mutex.try_lock()?;
+        Some(LeakCheckedGuard::new(guard, &self.held_at))
+    }
+
+    /// Asserts that no guard of this mutex is outstanding.
+    ///
+    /// # Panics
+    /// Panics if a guard is held, or was leaked, reporting where it was
+    /// acquired.
+    ///
+    /// # Examples
+    /// ```should_panic
+    /// use skirt::sync::LeakCheckedMutex;
+    ///
+    /// let mutex = LeakCheckedMutex::new(0);
+    /// let _guard = mutex.lock();
+    ///
+    /// mutex.checkpoint();
+    /// ```
+    #[track_caller]
+    pub fn checkpoint(&self) {
+        if let Some(location) = self.held_at() {
+            assert!(
+                panicking(),
+                "guard acquired at {location} is still held at checkpoint"
+            );
+        }
+    }
+
+    /// Returns where the outstanding guard of this mutex was acquired, or
+    /// [`None`] if there is none.
+    #[must_use]
+    pub fn held_at(&self) -> Option<&'static Location<'static>> {
+        let location = self.held_at.load(Ordering::Acquire);
+
+        // SAFETY: Only `&'static Location` references are stored.
+        unsafe { location.as_ref() }
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `LeakCheckedMutex` mutably, no actual
+    /// locking needs to take place.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.mutex.get_mut()
+    }
+
+    #[track_caller]
+    fn check_leaks(&self) {
+        if let Some(location) = self.held_at() {
+            assert!(panicking(), "guard acquired at {location} was leaked");
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for LeakCheckedMutex<T> {
+    fn drop(&mut self) {
+        self.check_leaks();
+    }
+}
+
+impl<T: Default> Default for LeakCheckedMutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for LeakCheckedMutex<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("LeakCheckedMutex");
+
+        match self.mutex.try_lock() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+
+        d.field("held_at", &self.held_at()).finish()
+    }
+}
+
+/// An RAII guard of a [`LeakCheckedMutex`].
+///
+/// This structure is created by the [`lock`] and [`try_lock`] methods on
+/// [`LeakCheckedMutex`].
+///
+/// [`lock`]: LeakCheckedMutex::lock
+/// [`try_lock`]: LeakCheckedMutex::try_lock
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this mutex while it is held"
+)]
+pub struct LeakCheckedGuard<'m, T: ?Sized> {
+    guard: MutexGuard<'m, T>,
+    held_at: &'m AtomicPtr<Location<'static>>,
+}
+
+impl<'m, T: ?Sized> LeakCheckedGuard<'m, T> {
+    #[track_caller]
+    fn new(guard: MutexGuard<'m, T>, held_at: &'m AtomicPtr<Location<'static>>) -> Self {
+        let location = ptr::from_ref(Location::caller()).cast_mut();
+        held_at.store(location, Ordering::Release);
+
+        Self { guard, held_at }
+    }
+}
+
+impl<T: ?Sized> Deref for LeakCheckedGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<T: ?Sized> DerefMut for LeakCheckedGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<T: ?Sized> Drop for LeakCheckedGuard<'_, T> {
+    fn drop(&mut self) {
+        // This runs before the inner guard releases the lock, so the next
+        // owner's location is never overwritten.
+        self.held_at.store(ptr::null_mut(), Ordering::Release);
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for LeakCheckedGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for LeakCheckedGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+fn panicking() -> bool {
+    std::thread::panicking()
+}
+
+#[cfg(not(feature = "std"))]
+const fn panicking() -> bool {
+    false
+}
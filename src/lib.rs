@@ -1,12 +1,80 @@
 //! # Skirt
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
-#![cfg_attr(feature = "nightly", feature(negative_impls))]
+#![cfg_attr(feature = "nightly", feature(negative_impls, must_not_suspend))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod atomic_enum;
+#[cfg(feature = "std")]
+mod atomic_instant;
+mod atomic_waker;
+mod biased_rwlock;
+mod bitset;
+mod blocking_guard;
+mod brlock;
+mod cache_padded;
+mod completion;
+mod context;
+#[cfg(feature = "alloc")]
+mod cow_lock;
+#[cfg(feature = "std")]
+mod deadline;
+mod error;
+mod exclusive_once;
+mod fair_rwlock;
+mod flag;
+mod global_slot;
+mod guarded;
+mod id;
+mod init_flag;
+#[cfg(feature = "alloc")]
+mod interner;
+mod isr_queue;
+mod isr_rwlock;
 mod lazy_lock;
+mod lazy_with;
+mod leak_checked;
+mod local_mutex;
+mod lock_all;
+mod lock_array;
 mod mutex;
 mod once;
+mod once_group;
 mod once_lock;
-// mod rwlock;
+mod once_per_key;
+mod periodic;
+mod pool;
+mod priority_mutex;
+mod profile;
+mod raw_byte_lock;
+mod register_lock;
+mod relax_strategy;
+mod retry_lazy;
+mod rwlock;
+mod rwlock_policy;
+mod sharded_counter;
+#[cfg(feature = "std")]
+mod thread_pool;
+#[cfg(feature = "std")]
+mod timed_hold;
+mod triple_buffer;
+mod wait_map;
+mod work_queue;
+
+pub use profile::{Profile, configure, profile};
+
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod init;
+pub mod metrics;
+#[cfg(feature = "testkit")]
+pub mod testkit;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::sync::atomic::{AtomicU8, Ordering};
+}
 
 /// Synchronization primitives that rely on spin-locking mechanisms.
 pub mod sync;
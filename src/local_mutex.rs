@@ -0,0 +1,176 @@
+use core::cell::{Cell, UnsafeCell};
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+
+/// A mutual exclusion primitive for data that never leaves its thread.
+///
+/// This has the same API as [`Mutex`], but tracks the lock with a plain
+/// [`Cell`] instead of an atomic, so it is neither [`Sync`] nor does it pay
+/// for atomic read-modify-write operations. It suits code that is generic
+/// over a mutex but runs on a single-threaded executor.
+///
+/// As there is no other thread that could release the lock, [`lock`] panics
+/// instead of spinning forever if the mutex is already locked.
+///
+/// [`Mutex`]: crate::sync::Mutex
+/// [`lock`]: Self::lock
+///
+/// # Examples
+/// ```
+/// use skirt::sync::LocalMutex;
+///
+/// let mutex = LocalMutex::new(0);
+///
+/// *mutex.lock() += 1;
+/// assert_eq!(*mutex.lock(), 1);
+///
+/// let guard = mutex.lock();
+/// assert!(mutex.try_lock().is_none());
+/// # drop(guard);
+/// ```
+pub struct LocalMutex<T: ?Sized> {
+    locked: Cell<bool>,
+    data: UnsafeCell<T>,
+}
+
+impl<T> LocalMutex<T> {
+    /// Creates a new mutex in an unlocked state ready for use.
+    #[inline]
+    pub const fn new(data: T) -> Self {
+        Self {
+            locked: Cell::new(false),
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consumes this mutex, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> LocalMutex<T> {
+    /// Acquires the mutex.
+    ///
+    /// # Panics
+    /// Panics if the mutex is already locked, which on a single thread would
+    /// otherwise never be released.
+    #[track_caller]
+    pub fn lock(&self) -> LocalMutexGuard<'_, T> {
+        self.try_lock()
+            .expect("LocalMutex is already locked by this thread")
+    }
+
+    /// Attempts to acquire the mutex, returning [`None`] if it is locked.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::LocalMutex;
+    ///
+    /// let mutex = LocalMutex::new(0);
+    /// let guard = mutex.lock();
+    ///
+    /// // A failed attempt leaves the mutex locked by `guard`.
+    /// assert!(mutex.try_lock().is_none());
+    /// assert!(mutex.is_locked());
+    /// assert!(mutex.try_lock().is_none());
+    ///
+    /// drop(guard);
+    /// assert!(mutex.try_lock().is_some());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn try_lock(&self) -> Option<LocalMutexGuard<'_, T>> {
+        if self.locked.replace(true) {
+            None
+        } else {
+            Some(LocalMutexGuard { mutex: self })
+        }
+    }
+
+    /// Returns whether the mutex is locked.
+    #[inline]
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.locked.get()
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `LocalMutex` mutably, no actual locking
+    /// needs to take place.
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+}
+
+impl<T> From<T> for LocalMutex<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: Default> Default for LocalMutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for LocalMutex<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("LocalMutex");
+
+        match self.try_lock() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+
+        d.finish_non_exhaustive()
+    }
+}
+
+/// An RAII guard of a [`LocalMutex`], which unlocks it when dropped.
+///
+/// This structure is created by the [`lock`] and [`try_lock`] methods on
+/// [`LocalMutex`].
+///
+/// [`lock`]: LocalMutex::lock
+/// [`try_lock`]: LocalMutex::try_lock
+pub struct LocalMutexGuard<'m, T: ?Sized> {
+    mutex: &'m LocalMutex<T>,
+}
+
+impl<T: ?Sized> Deref for LocalMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for LocalMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for LocalMutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.mutex.locked.set(false);
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for LocalMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for LocalMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
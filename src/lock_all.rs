@@ -0,0 +1,69 @@
+use crate::mutex::{Mutex, MutexGuard};
+
+/// A tuple of mutex references that can be locked all at once.
+///
+/// This is implemented for tuples of up to eight `&Mutex<_>`, and is used
+/// through [`try_lock_all`].
+pub trait TryLockAll<'a> {
+    /// The tuple of guards returned when every lock was acquired.
+    type Guards;
+
+    /// Attempts to acquire every lock without blocking.
+    fn try_lock_all(self) -> Option<Self::Guards>;
+}
+
+/// Attempts to acquire every mutex in `locks` without blocking.
+///
+/// Either every lock is acquired and their guards are returned in order, or
+/// the locks acquired so far are released again and [`None`] is returned.
+/// Since nothing is ever waited on, this cannot deadlock regardless of the
+/// order in which different threads pass their locks.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::{Mutex, try_lock_all};
+///
+/// let a = Mutex::new(1);
+/// let b = Mutex::new(2);
+/// let c = Mutex::new(3);
+///
+/// let (mut a_guard, b_guard, c_guard) = try_lock_all((&a, &b, &c)).unwrap();
+/// *a_guard += *b_guard + *c_guard;
+/// drop((a_guard, b_guard, c_guard));
+///
+/// let b_guard = b.lock();
+/// assert!(try_lock_all((&a, &b, &c)).is_none());
+///
+/// // `a` was rolled back and is free again.
+/// assert_eq!(*a.try_lock().unwrap(), 6);
+/// # drop(b_guard);
+/// ```
+pub fn try_lock_all<'a, L: TryLockAll<'a>>(locks: L) -> Option<L::Guards> {
+    locks.try_lock_all()
+}
+
+macro_rules! impl_try_lock_all {
+    ($($name:ident: $ty:ident),+) => {
+        impl<'a, $($ty: ?Sized),+> TryLockAll<'a> for ($(&'a Mutex<$ty>,)+) {
+            type Guards = ($(MutexGuard<'a, $ty>,)+);
+
+            #[inline]
+            fn try_lock_all(self) -> Option<Self::Guards> {
+                let ($($name,)+) = self;
+
+                // Guards acquired before a failing `?` are dropped on return,
+                // which releases them again.
+                Some(($($name.try_lock()?,)+))
+            }
+        }
+    };
+}
+
+impl_try_lock_all!(a: A);
+impl_try_lock_all!(a: A, b: B);
+impl_try_lock_all!(a: A, b: B, c: C);
+impl_try_lock_all!(a: A, b: B, c: C, d: D);
+impl_try_lock_all!(a: A, b: B, c: C, d: D, e: E);
+impl_try_lock_all!(a: A, b: B, c: C, d: D, e: E, f: F);
+impl_try_lock_all!(a: A, b: B, c: C, d: D, e: E, f: F, g: G);
+impl_try_lock_all!(a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H);
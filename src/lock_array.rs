@@ -0,0 +1,175 @@
+use crate::cache_padded::CachePadded;
+use crate::raw_byte_lock::RawByteLock;
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+
+/// An array of `N` values, each protected by its own one-byte lock.
+///
+/// This is the usual shape of bucket locking in a hash table: locking one
+/// element never blocks threads working on another. The locks are packed
+/// together as [`RawByteLock`]s and padded to a cache line of their own, so
+/// taking a lock does not invalidate the cache line of the values being
+/// worked on by other threads.
+///
+/// Packing keeps the array small, at the cost of the locks sharing cache
+/// lines with each other: threads locking different elements at a high rate
+/// still pass those lines between their cores. Where that matters more than
+/// memory, give each element its own [`Mutex`] padded to a cache line.
+///
+/// [`Mutex`]: crate::sync::Mutex
+///
+/// # Examples
+/// ```
+/// use skirt::sync::LockArray;
+///
+/// static BUCKETS: LockArray<u32, 4> = LockArray::new([0; 4]);
+///
+/// let mut first = BUCKETS.lock(0);
+/// let mut second = BUCKETS.lock(1);
+/// *first += 1;
+/// *second += 2;
+///
+/// assert!(BUCKETS.try_lock(0).is_none());
+/// drop(first);
+/// assert_eq!(*BUCKETS.lock(0), 1);
+/// ```
+pub struct LockArray<T, const N: usize> {
+    locks: CachePadded<[RawByteLock; N]>,
+    values: UnsafeCell<[T; N]>,
+}
+
+unsafe impl<T: Send, const N: usize> Send for LockArray<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for LockArray<T, N> {}
+
+impl<T, const N: usize> LockArray<T, N> {
+    /// Creates a new array holding `values`, all of which are unlocked.
+    #[inline]
+    pub const fn new(values: [T; N]) -> Self {
+        Self {
+            locks: CachePadded::new([const { RawByteLock::new() }; N]),
+            values: UnsafeCell::new(values),
+        }
+    }
+
+    /// Locks the element at `index`, blocking the current thread until it is
+    /// able to do so.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    pub fn lock(&self, index: usize) -> LockArrayGuard<'_, T, N> {
+        self.locks[index].lock();
+        LockArrayGuard { array: self, index }
+    }
+
+    /// Attempts to lock the element at `index` without blocking.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    #[must_use]
+    pub fn try_lock(&self, index: usize) -> Option<LockArrayGuard<'_, T, N>> {
+        self.locks[index]
+            .try_lock()
+            .then(|| LockArrayGuard { array: self, index })
+    }
+
+    /// Returns the number of elements in the array.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns whether the array has no elements.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns a mutable reference to the element at `index`, or [`None`] if
+    /// it is out of bounds.
+    ///
+    /// Since this call borrows the array mutably, no locking needs to take
+    /// place.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.values.get_mut().get_mut(index)
+    }
+
+    /// Consumes this array, returning the values it holds.
+    pub fn into_inner(self) -> [T; N] {
+        self.values.into_inner()
+    }
+}
+
+impl<T: Default, const N: usize> Default for LockArray<T, N> {
+    fn default() -> Self {
+        Self::new(core::array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> Debug for LockArray<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LockArray")
+            .field(
+                "locked",
+                &self.locks.iter().filter(|lock| lock.is_locked()).count(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// RAII structure giving exclusive access to one element of a
+/// [`LockArray`], which is unlocked when dropped.
+///
+/// This structure is created by the [`lock`] and [`try_lock`] methods on
+/// [`LockArray`].
+///
+/// [`lock`]: LockArray::lock
+/// [`try_lock`]: LockArray::try_lock
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this element while it is held"
+)]
+pub struct LockArrayGuard<'a, T, const N: usize> {
+    array: &'a LockArray<T, N>,
+    index: usize,
+}
+
+unsafe impl<T: Sync, const N: usize> Sync for LockArrayGuard<'_, T, N> {}
+
+impl<T, const N: usize> Deref for LockArrayGuard<'_, T, N> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The element is locked, giving us exclusive access.
+        unsafe { &*self.array.values.get().cast::<T>().add(self.index) }
+    }
+}
+
+impl<T, const N: usize> DerefMut for LockArrayGuard<'_, T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The element is locked, giving us exclusive access.
+        unsafe { &mut *self.array.values.get().cast::<T>().add(self.index) }
+    }
+}
+
+impl<T, const N: usize> Drop for LockArrayGuard<'_, T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: The guard holds the lock.
+        unsafe { self.array.locks[self.index].unlock() };
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for LockArrayGuard<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Display, const N: usize> Display for LockArrayGuard<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
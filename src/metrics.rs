@@ -0,0 +1,289 @@
+//! Lock-free counters, gauges and histograms.
+//!
+//! Every update is a single atomic read-modify-write, so instrumenting lock
+//! heavy or interrupt-driven code never introduces a lock of its own. Readers
+//! take snapshots at any time, from any context.
+//!
+//! Values are `usize` so that the types work on every target, including
+//! those without 64-bit atomics.
+//!
+//! # Examples
+//! ```
+//! use skirt::metrics::{Counter, Gauge, Histogram};
+//!
+//! static ACQUIRED: Counter = Counter::new();
+//! static WAITERS: Gauge = Gauge::new();
+//! static SPINS: Histogram<3> = Histogram::new([1, 10, 100]);
+//!
+//! WAITERS.inc();
+//! SPINS.record(42);
+//! ACQUIRED.inc();
+//! WAITERS.dec();
+//!
+//! assert_eq!(ACQUIRED.get(), 1);
+//! assert_eq!(WAITERS.get(), 0);
+//! assert_eq!(SPINS.snapshot().buckets, [0, 0, 1]);
+//! ```
+
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A monotonically increasing count of events.
+#[derive(Default)]
+pub struct Counter {
+    value: AtomicUsize,
+}
+
+impl Counter {
+    /// Creates a new counter at zero.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            value: AtomicUsize::new(0),
+        }
+    }
+
+    /// Counts one event.
+    #[inline]
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Counts `n` events. The count wraps around on overflow.
+    #[inline]
+    pub fn add(&self, n: usize) {
+        self.value.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Returns the current count.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> usize {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    /// Resets the count to zero, returning the count so far.
+    ///
+    /// Events counted concurrently are either included in the returned count
+    /// or in the next one, never lost.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::metrics::Counter;
+    ///
+    /// let counter = Counter::new();
+    /// counter.add(3);
+    ///
+    /// assert_eq!(counter.reset(), 3);
+    /// assert_eq!(counter.get(), 0);
+    /// ```
+    pub fn reset(&self) -> usize {
+        self.value.swap(0, Ordering::Relaxed)
+    }
+}
+
+impl Debug for Counter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Counter").field(&self.get()).finish()
+    }
+}
+
+/// A value that goes up and down, such as a queue depth or the number of
+/// waiters on a lock.
+#[derive(Default)]
+pub struct Gauge {
+    value: AtomicUsize,
+}
+
+impl Gauge {
+    /// Creates a new gauge at zero.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            value: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the gauge to `value`.
+    #[inline]
+    pub fn set(&self, value: usize) {
+        self.value.store(value, Ordering::Relaxed);
+    }
+
+    /// Increments the gauge by one.
+    #[inline]
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Decrements the gauge by one.
+    #[inline]
+    pub fn dec(&self) {
+        self.sub(1);
+    }
+
+    /// Adds `n` to the gauge, wrapping around on overflow.
+    #[inline]
+    pub fn add(&self, n: usize) {
+        self.value.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Subtracts `n` from the gauge, wrapping around on underflow.
+    #[inline]
+    pub fn sub(&self, n: usize) {
+        self.value.fetch_sub(n, Ordering::Relaxed);
+    }
+
+    /// Raises the gauge to `value` if it is lower, which tracks a high-water
+    /// mark.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::metrics::Gauge;
+    ///
+    /// let peak = Gauge::new();
+    /// peak.set_max(4);
+    /// peak.set_max(2);
+    ///
+    /// assert_eq!(peak.get(), 4);
+    /// ```
+    #[inline]
+    pub fn set_max(&self, value: usize) {
+        self.value.fetch_max(value, Ordering::Relaxed);
+    }
+
+    /// Returns the current value.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> usize {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+impl Debug for Gauge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Gauge").field(&self.get()).finish()
+    }
+}
+
+/// A distribution of values over `N` fixed buckets.
+///
+/// Each bucket counts the values up to and including its upper bound, and
+/// greater than the bound of the bucket before it. Values above the last
+/// bound are counted separately as overflow.
+///
+/// # Examples
+/// ```
+/// use skirt::metrics::Histogram;
+///
+/// let latency = Histogram::new([10, 100, 1000]);
+///
+/// for micros in [3, 10, 11, 500, 5000] {
+///     latency.record(micros);
+/// }
+///
+/// let snapshot = latency.snapshot();
+/// assert_eq!(snapshot.buckets, [2, 1, 1]);
+/// assert_eq!(snapshot.overflow, 1);
+/// assert_eq!(snapshot.count(), 5);
+/// assert_eq!(snapshot.sum, 5524);
+/// ```
+pub struct Histogram<const N: usize> {
+    bounds: [usize; N],
+    buckets: [AtomicUsize; N],
+    overflow: AtomicUsize,
+    sum: AtomicUsize,
+}
+
+impl<const N: usize> Histogram<N> {
+    /// Creates a new, empty histogram with the given bucket upper bounds.
+    ///
+    /// # Panics
+    /// Panics if `bounds` is not strictly increasing.
+    #[must_use]
+    pub const fn new(bounds: [usize; N]) -> Self {
+        let mut i = 1;
+
+        while i < N {
+            assert!(
+                bounds[i - 1] < bounds[i],
+                "histogram bounds must be strictly increasing"
+            );
+            i += 1;
+        }
+
+        Self {
+            bounds,
+            buckets: [const { AtomicUsize::new(0) }; N],
+            overflow: AtomicUsize::new(0),
+            sum: AtomicUsize::new(0),
+        }
+    }
+
+    /// Records `value` in its bucket.
+    ///
+    /// The sum of all values wraps around on overflow.
+    pub fn record(&self, value: usize) {
+        let bucket = self
+            .bounds
+            .iter()
+            .position(|&bound| value <= bound)
+            .map_or(&self.overflow, |index| &self.buckets[index]);
+
+        bucket.fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// Takes a snapshot of the histogram.
+    ///
+    /// Each count is read atomically, but values recorded while the
+    /// snapshot is taken may be included in some counts and not others.
+    #[must_use]
+    pub fn snapshot(&self) -> HistogramSnapshot<N> {
+        HistogramSnapshot {
+            bounds: self.bounds,
+            buckets: core::array::from_fn(|i| self.buckets[i].load(Ordering::Relaxed)),
+            overflow: self.overflow.load(Ordering::Relaxed),
+            sum: self.sum.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the bucket upper bounds.
+    #[inline]
+    #[must_use]
+    pub const fn bounds(&self) -> &[usize; N] {
+        &self.bounds
+    }
+}
+
+impl<const N: usize> Debug for Histogram<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Histogram").field(&self.snapshot()).finish()
+    }
+}
+
+/// The counts of a [`Histogram`] at one point in time, returned by
+/// [`Histogram::snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HistogramSnapshot<const N: usize> {
+    /// The upper bound of each bucket.
+    pub bounds: [usize; N],
+    /// The number of values recorded in each bucket.
+    pub buckets: [usize; N],
+    /// The number of values above the last bound.
+    pub overflow: usize,
+    /// The sum of all recorded values, wrapped around on overflow.
+    pub sum: usize,
+}
+
+impl<const N: usize> HistogramSnapshot<N> {
+    /// Returns the number of recorded values.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.buckets
+            .iter()
+            .fold(self.overflow, |count, &bucket| count.wrapping_add(bucket))
+    }
+}
@@ -1,13 +1,26 @@
-use crate::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use crate::deadline::Deadline;
+#[cfg(feature = "std")]
+use crate::error::TimeoutError;
+use crate::error::{PoisonError, SpinBudgetExceeded, TryLockError};
+use crate::profile::Backoff;
+use crate::relax_strategy::{Configured, Park, RelaxStrategy, Spin, Yield};
+use crate::sync::atomic::{AtomicU8, Ordering};
+use core::alloc::Layout;
 use core::cell::UnsafeCell;
 use core::fmt::{Debug, Display, Formatter};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
 
 /// A mutual exclusion primitive useful for protecting shared data.
 ///
 /// This mutex will block thread waiting for the lock to become available.
 /// In a `no_std` environment, the mutex employs a spin-lock mechanism, continiously checking for availability.
-/// In a `std` environment, the mutex will yield the thread.
+/// In a `std` environment, the mutex waits according to its [`RelaxStrategy`] `R`, which by default follows
+/// the [`Profile`](crate::Profile) set with [`configure`](crate::configure). The [`SpinMutex`], [`YieldMutex`]
+/// and [`ParkMutex`] aliases fix the strategy instead, and are created with [`with_strategy`].
 /// The mutex can be created via a [`new`] constructor.
 /// Each mutex has a type parameter which represents the data that it is protecting.
 /// The data can only be accessed through the RAII guards returned from [`lock`] and [`try_lock`],
@@ -16,15 +29,245 @@
 /// [`new`]: Self::new
 /// [`lock`]: Self::lock
 /// [`try_lock`]: Self::try_lock
+/// [`with_strategy`]: Self::with_strategy
+///
+/// # Layout
+/// `Mutex<T>` is `#[repr(C)]`: the lock is a single byte at offset `0`,
+/// followed by the protected data at [`DATA_OFFSET`]. The layout is the same
+/// under every feature set and strategy, since Cargo may enable features such as `poison`
+/// on behalf of another crate in the dependency graph. This makes it suitable
+/// for embedding in shared-memory or hardware-defined structures that require
+/// stable offsets. The layout can be checked at compile time:
+///
+/// ```
+/// use skirt::sync::Mutex;
+///
+/// const _: () = assert!(<Mutex<u8>>::DATA_OFFSET == 1);
+/// const _: () = assert!(<Mutex<u32>>::DATA_OFFSET == 4);
+/// const _: () = assert!(<Mutex<u32>>::layout().size() == 8);
+/// ```
+///
+/// [`DATA_OFFSET`]: Self::DATA_OFFSET
+///
+/// # Leaked guards
+/// Leaking a [`MutexGuard`], for example with [`mem::forget`] or because the
+/// task holding it was killed, leaves the mutex locked forever: every later
+/// [`lock`] spins indefinitely and [`try_lock`] always fails. The data itself
+/// is never corrupted by a leak. A system that knows the owner is gone can
+/// recover the mutex with [`force_reset`].
+///
+/// [`mem::forget`]: core::mem::forget
+/// [`force_reset`]: Self::force_reset
+///
+/// # Panics while locked
+/// By default, a guard dropped while its thread panics unlocks the mutex
+/// like any other. Two features change this for every mutex:
+///
+/// - `poison` marks the mutex as poisoned, which can be checked with
+///   [`is_poisoned`] and reset with [`clear_poison`]. Unlike the standard
+///   library, [`lock`] still succeeds on a poisoned mutex, while
+///   [`lock_checked`] reports it with a [`PoisonError`] like the standard
+///   library's `lock` does.
+/// - `abort_on_panic` aborts the process instead, for deployments that must
+///   fail fast rather than continue with possibly inconsistent data. It takes
+///   precedence over `poison`.
+///
+/// Both require the `std` feature.
+///
+/// [`is_poisoned`]: Self::is_poisoned
+/// [`clear_poison`]: Self::clear_poison
+/// [`lock_checked`]: Self::lock_checked
+/// [`PoisonError`]: crate::sync::PoisonError
 ///
 /// # Examples
-pub struct Mutex<T: ?Sized> {
-    lock: AtomicBool,
+#[repr(C)]
+pub struct Mutex<T: ?Sized, R: RelaxStrategy = Configured> {
+    lock: State,
+    strategy: PhantomData<R>,
     data: UnsafeCell<T>,
 }
 
-unsafe impl<T: ?Sized + Send> Send for Mutex<T> {}
-unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
+/// A [`Mutex`] that only ever spins while waiting, whatever the configured
+/// [`Profile`](crate::Profile).
+///
+/// # Examples
+/// ```
+/// use skirt::sync::{Mutex, Spin, SpinMutex};
+///
+/// static COUNTER: SpinMutex<u32> = Mutex::with_strategy(0, Spin);
+///
+/// *COUNTER.lock() += 1;
+/// assert_eq!(*COUNTER.lock(), 1);
+/// ```
+pub type SpinMutex<T> = Mutex<T, Spin>;
+
+/// A [`Mutex`] that yields the thread after every failed attempt to acquire
+/// it, whatever the configured [`Profile`](crate::Profile).
+pub type YieldMutex<T> = Mutex<T, Yield>;
+
+/// A [`Mutex`] that parks the thread while waiting for long, whatever the
+/// configured [`Profile`](crate::Profile). See [`Park`](crate::sync::Park).
+///
+/// # Examples
+/// ```
+/// use skirt::sync::{Mutex, Park, ParkMutex};
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let mutex: Arc<ParkMutex<u32>> = Arc::new(Mutex::with_strategy(0, Park));
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let mutex = Arc::clone(&mutex);
+///         thread::spawn(move || {
+///             for _ in 0..100 {
+///                 *mutex.lock() += 1;
+///             }
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+///
+/// assert_eq!(*mutex.lock(), 400);
+/// ```
+pub type ParkMutex<T> = Mutex<T, Park>;
+
+/// Set while the mutex is locked.
+const LOCKED: u8 = 1;
+/// Set once a guard was dropped by a panicking thread, with the `poison`
+/// feature. It shares the byte with [`LOCKED`] to keep the layout stable.
+const POISONED: u8 = 1 << 1;
+
+/// The lock word of a [`Mutex`], which mapped guards release without
+/// knowing the type of the data.
+#[repr(C)]
+struct State {
+    state: AtomicU8,
+}
+
+impl State {
+    const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(0),
+        }
+    }
+
+    /// Attempts to set the locked bit, keeping the poisoned bit as it is.
+    #[inline]
+    fn try_lock(&self, weak: bool) -> bool {
+        // Without the `poison` feature, the only unlocked state is zero.
+        let unlocked = if cfg!(feature = "poison") {
+            self.state.load(Ordering::Relaxed) & POISONED
+        } else {
+            0
+        };
+
+        let locked = unlocked | LOCKED;
+
+        if weak {
+            self.state
+                .compare_exchange_weak(unlocked, locked, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+        } else {
+            self.state
+                .compare_exchange(unlocked, locked, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+        }
+    }
+
+    #[inline]
+    fn is_locked(&self) -> bool {
+        self.state.load(Ordering::Relaxed) & LOCKED != 0
+    }
+
+    /// Clears the locked bit, keeping the poisoned bit as it is.
+    #[inline]
+    fn unlock(&self) {
+        #[cfg(feature = "poison")]
+        self.state.fetch_and(!LOCKED, Ordering::Release);
+
+        #[cfg(not(feature = "poison"))]
+        self.state.store(0, Ordering::Release);
+    }
+
+    /// Unlocks the mutex, applying the panic strategy first if the current
+    /// thread started panicking while holding it.
+    #[inline]
+    fn release(&self, acquired: Acquired) {
+        if acquired.panicked_since() {
+            #[cfg(feature = "abort_on_panic")]
+            std::process::abort();
+
+            #[cfg(all(feature = "poison", not(feature = "abort_on_panic")))]
+            self.state.fetch_or(POISONED, Ordering::Relaxed);
+        }
+
+        self.unlock();
+    }
+}
+
+/// Whether the thread holding a guard was already panicking when it locked
+/// the mutex, like the standard library's poison guard. A guard taken while
+/// unwinding, for example in a destructor, releases the lock normally.
+#[derive(Clone, Copy)]
+struct Acquired {
+    #[cfg(any(feature = "poison", feature = "abort_on_panic"))]
+    panicking: bool,
+}
+
+impl Acquired {
+    /// For a guard whose acquisition is unknown, such as one rebuilt from a
+    /// raw pointer. Any panic in progress at release applies the strategy.
+    const UNKNOWN: Self = Self {
+        #[cfg(any(feature = "poison", feature = "abort_on_panic"))]
+        panicking: false,
+    };
+}
+
+#[cfg(any(feature = "poison", feature = "abort_on_panic"))]
+impl Acquired {
+    #[inline]
+    fn now() -> Self {
+        Self {
+            panicking: std::thread::panicking(),
+        }
+    }
+
+    /// Returns whether the current thread started panicking since the lock
+    /// was acquired.
+    #[inline]
+    fn panicked_since(self) -> bool {
+        !self.panicking && std::thread::panicking()
+    }
+}
+
+// Without a panic strategy, nothing needs to know.
+#[cfg(not(any(feature = "poison", feature = "abort_on_panic")))]
+impl Acquired {
+    #[inline]
+    const fn now() -> Self {
+        Self {}
+    }
+
+    #[inline]
+    #[allow(clippy::unused_self)]
+    const fn panicked_since(self) -> bool {
+        false
+    }
+}
+
+unsafe impl<T: ?Sized + Send, R: RelaxStrategy> Send for Mutex<T, R> {}
+unsafe impl<T: ?Sized + Send, R: RelaxStrategy> Sync for Mutex<T, R> {}
+
+#[cfg(feature = "poison")]
+impl<T: ?Sized, R: RelaxStrategy> std::panic::UnwindSafe for Mutex<T, R> {}
+#[cfg(feature = "poison")]
+impl<T: ?Sized, R: RelaxStrategy> std::panic::RefUnwindSafe for Mutex<T, R> {}
+
+const _: () = assert!(size_of::<State>() == 1);
 
 impl<T> Mutex<T> {
     /// Creates a new mutex in an unlocked state ready for use.
@@ -37,12 +280,122 @@ impl<T> Mutex<T> {
     /// ```
     #[inline]
     pub const fn new(data: T) -> Self {
+        Self::with_strategy(data, Configured)
+    }
+
+    /// Creates a new mutex whose data is initialized in place by `f`.
+    ///
+    /// `f` writes the data directly into the mutex, so only the finished
+    /// mutex is moved out, not a separately built value. To avoid that move
+    /// as well, see [`emplace`](Self::emplace).
+    ///
+    /// # Safety
+    /// `f` must fully initialize the data it is given before returning.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// // SAFETY: The closure initializes the data.
+    /// let mutex = unsafe { Mutex::new_with(|data| { data.write([7u8; 64]); }) };
+    /// assert_eq!(mutex.lock()[63], 7);
+    /// ```
+    #[inline]
+    pub unsafe fn new_with<F>(f: F) -> Self
+    where
+        F: FnOnce(&mut MaybeUninit<T>),
+    {
+        let mut slot = MaybeUninit::uninit();
+
+        // SAFETY: Forwarded to the caller.
+        unsafe {
+            Self::emplace(&mut slot, f);
+            slot.assume_init()
+        }
+    }
+}
+
+impl<T, R: RelaxStrategy> Mutex<T, R> {
+    /// The offset in bytes of the protected data from the start of the mutex.
+    pub const DATA_OFFSET: usize = core::mem::offset_of!(Self, data);
+
+    /// Returns the memory layout of a `Mutex<T, R>`.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let layout = <Mutex<u16>>::layout();
+    /// assert_eq!(layout.size(), 4);
+    /// assert_eq!(layout.align(), 2);
+    /// ```
+    #[must_use]
+    pub const fn layout() -> Layout {
+        Layout::new::<Self>()
+    }
+
+    /// Creates a new mutex in an unlocked state, which waits according to
+    /// the strategy `R`.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Mutex, Yield};
+    ///
+    /// let mutex = Mutex::with_strategy(0, Yield);
+    ///
+    /// *mutex.lock() += 1;
+    /// assert_eq!(*mutex.lock(), 1);
+    /// ```
+    #[inline]
+    pub const fn with_strategy(data: T, _strategy: R) -> Self {
         Self {
-            lock: AtomicBool::new(false),
+            lock: State::new(),
+            strategy: PhantomData,
             data: UnsafeCell::new(data),
         }
     }
 
+    /// Initializes a mutex in `slot`, letting `f` initialize the data in
+    /// place, and returns a reference to it.
+    ///
+    /// This builds a mutex without moving it at all, such as a large buffer
+    /// in a static or in a heap allocation.
+    ///
+    /// # Safety
+    /// `f` must fully initialize the data it is given before returning.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let mut slot = Box::<Mutex<[u8; 4096]>>::new_uninit();
+    ///
+    /// // SAFETY: `write_bytes` initializes the whole buffer.
+    /// unsafe {
+    ///     Mutex::emplace(&mut slot, |data| data.as_mut_ptr().write_bytes(0, 1));
+    /// }
+    ///
+    /// // SAFETY: `emplace` initialized the mutex.
+    /// let mutex = unsafe { slot.assume_init() };
+    /// assert!(mutex.lock().iter().all(|&byte| byte == 0));
+    /// ```
+    pub unsafe fn emplace<F>(slot: &mut MaybeUninit<Self>, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut MaybeUninit<T>),
+    {
+        let ptr = slot.as_mut_ptr();
+
+        // SAFETY: The fields are written through raw pointers without reading
+        // the uninitialized mutex, and `UnsafeCell<T>` has the same layout as
+        // `T`. The caller guarantees that `f` initializes the data.
+        unsafe {
+            (&raw mut (*ptr).lock).write(State::new());
+            (&raw mut (*ptr).strategy).write(PhantomData);
+            f(&mut *(&raw mut (*ptr).data).cast::<MaybeUninit<T>>());
+            slot.assume_init_mut()
+        }
+    }
+
     /// Returns the contained value by cloning it.
     ///
     /// # Examples
@@ -60,6 +413,56 @@ pub fn get_cloned(&self) -> T
         (*self.lock()).clone()
     }
 
+    /// Returns a copy of the contained value, holding the lock only for as
+    /// long as the copy takes.
+    ///
+    /// This is `*self.lock()`, and waits for the lock like [`lock`] does.
+    /// Copying the value out is often better than holding a guard across
+    /// other work, since it keeps the critical section as short as possible.
+    /// For copies that never wait for a writer, see
+    /// [`RwLock::read_optimistic`](crate::sync::RwLock::read_optimistic).
+    ///
+    /// [`lock`]: Self::lock
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let position = Mutex::new((3, 4));
+    ///
+    /// let (x, y) = position.read_copy();
+    /// assert_eq!(x * x + y * y, 25);
+    /// ```
+    #[inline]
+    pub fn read_copy(&self) -> T
+    where
+        T: Copy,
+    {
+        *self.lock()
+    }
+
+    /// Returns the contained value by cloning it, or [`None`] if the mutex is
+    /// currently locked.
+    ///
+    /// This never blocks, which suits metrics and snapshot paths.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new(7);
+    /// assert_eq!(mutex.try_get_cloned(), Some(7));
+    ///
+    /// let guard = mutex.lock();
+    /// assert_eq!(mutex.try_get_cloned(), None);
+    /// ```
+    pub fn try_get_cloned(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.try_lock().map(|guard| (*guard).clone())
+    }
+
     /// Replaces the contained value with `data`, and returns the old contained value.
     ///
     /// # Examples
@@ -76,7 +479,7 @@ pub fn replace(&self, data: T) -> T {
     }
 }
 
-impl<T: ?Sized> Mutex<T> {
+impl<T: ?Sized, R: RelaxStrategy> Mutex<T, R> {
     /// Acquires a mutex, blocking the current thread until it is able to do so.
     ///
     /// # Examples
@@ -93,42 +496,76 @@ impl<T: ?Sized> Mutex<T> {
     /// }).join().expect("thread::spawn failed");
     /// assert_eq!(*mutex.lock(), 10);
     /// ```
-    pub fn lock(&self) -> MutexGuard<'_, T> {
-        #[cfg(feature = "std")]
-        let mut tries = 0;
+    pub fn lock(&self) -> MutexGuard<'_, T, R> {
+        #[cfg(feature = "chaos")]
+        crate::chaos::delay();
 
-        while self.try_lock().is_none() {
-            core::hint::spin_loop();
+        let mut backoff = Backoff::new();
 
-            #[cfg(feature = "std")]
-            match tries >= 10 {
-                true => std::thread::yield_now(),
-                false => tries += 1,
+        loop {
+            if let Some(guard) = self.try_acquire() {
+                return guard;
             }
+
+            R::relax(&mut backoff);
+        }
+    }
+
+    pub fn lock_weak(&self) -> MutexGuard<'_, T, R> {
+        #[cfg(feature = "chaos")]
+        crate::chaos::delay();
+
+        let mut backoff = Backoff::new();
+
+        while !self.lock.try_lock(true) {
+            R::relax(&mut backoff);
         }
 
-        MutexGuard::new(self)
+        MutexGuard::new(self, Acquired::now())
     }
 
-    pub fn lock_weak(&self) -> MutexGuard<'_, T> {
-        #[cfg(feature = "std")]
-        let mut tries = 0;
+    /// Acquires the mutex once `pred` holds for the protected data, blocking
+    /// the current thread until then.
+    ///
+    /// The predicate is re-checked under the lock, and the lock is released
+    /// between checks so that other threads can make progress. This is a
+    /// lightweight alternative to a condition variable for simple state-wait
+    /// loops.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let mutex = Arc::new(Mutex::new(0));
+    /// let c_mutex = Arc::clone(&mutex);
+    ///
+    /// thread::spawn(move || {
+    ///     for _ in 0..3 {
+    ///         *c_mutex.lock() += 1;
+    ///     }
+    /// });
+    ///
+    /// let guard = mutex.wait_until(|count| *count == 3);
+    /// assert_eq!(*guard, 3);
+    /// ```
+    pub fn wait_until<F>(&self, mut pred: F) -> MutexGuard<'_, T, R>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut backoff = Backoff::new();
 
-        while self
-            .lock
-            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
-            .is_err()
-        {
-            core::hint::spin_loop();
+        loop {
+            let guard = self.lock();
 
-            #[cfg(feature = "std")]
-            match tries >= 10 {
-                true => std::thread::yield_now(),
-                false => tries += 1,
+            if pred(&guard) {
+                return guard;
             }
-        }
 
-        MutexGuard::new(self)
+            drop(guard);
+            R::relax(&mut backoff);
+        }
     }
 
     /// Attempts to acquire this lock.
@@ -137,7 +574,8 @@ pub fn lock_weak(&self) -> MutexGuard<'_, T> {
     /// Otherwise, an RAII guard is returned. The lock will be unlocked when the
     /// guard is dropped.
     ///
-    /// This function does not block.
+    /// This function does not block. With the `chaos` feature, it also fails
+    /// at random while the lock is free.
     ///
     /// # Example
     /// ```
@@ -159,11 +597,366 @@ pub fn lock_weak(&self) -> MutexGuard<'_, T> {
     /// assert_eq!(*mutex.lock(), 10);
     /// ```
     #[must_use]
-    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T, R>> {
+        #[cfg(feature = "chaos")]
+        if crate::chaos::fail() {
+            return None;
+        }
+
+        self.try_acquire()
+    }
+
+    /// Attempts to acquire this lock, reporting why it failed like the
+    /// standard library's `Mutex::try_lock`.
+    ///
+    /// # Errors
+    /// Returns [`TryLockError::WouldBlock`] if the lock is held, and
+    /// [`TryLockError::Poisoned`] with the guard if the lock was acquired but
+    /// is poisoned. See [`lock_checked`].
+    ///
+    /// [`lock_checked`]: Self::lock_checked
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Mutex, TryLockError};
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let guard = mutex.try_lock_checked().unwrap();
+    ///
+    /// assert!(matches!(mutex.try_lock_checked(), Err(TryLockError::WouldBlock)));
+    /// # drop(guard);
+    /// ```
+    pub fn try_lock_checked(
+        &self,
+    ) -> Result<MutexGuard<'_, T, R>, TryLockError<MutexGuard<'_, T, R>>> {
+        let guard = self.try_lock().ok_or(TryLockError::WouldBlock)?;
+
+        #[cfg(feature = "poison")]
+        if self.is_poisoned() {
+            return Err(PoisonError::new(guard).into());
+        }
+
+        Ok(guard)
+    }
+
+    /// Acquires this lock, giving up once `deadline` has passed.
+    ///
+    /// # Errors
+    /// Returns [`TimeoutError`] if the lock could not be acquired before the
+    /// deadline.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Deadline, Mutex, TimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let guard = mutex.lock();
+    ///
+    /// let deadline = Deadline::after(Duration::from_millis(10));
+    /// assert_eq!(mutex.lock_until(&deadline).err(), Some(TimeoutError));
+    /// # drop(guard);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn lock_until(&self, deadline: &Deadline) -> Result<MutexGuard<'_, T, R>, TimeoutError> {
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Some(guard) = self.try_lock() {
+                return Ok(guard);
+            }
+
+            deadline.check()?;
+
+            R::relax(&mut backoff);
+        }
+    }
+
+    fn try_acquire(&self) -> Option<MutexGuard<'_, T, R>> {
         self.lock
-            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
-            .is_ok()
-            .then(|| MutexGuard::new(self))
+            .try_lock(false)
+            .then(|| MutexGuard::new(self, Acquired::now()))
+    }
+
+    /// Returns whether the mutex is currently held, meaning a call to
+    /// [`lock`] would have to wait.
+    ///
+    /// This is a single relaxed load, cheap enough for schedulers and
+    /// load-shedding code to consult before taking a slow path. It is only a
+    /// hint and may be outdated by the time it is used.
+    ///
+    /// [`lock`]: Self::lock
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// assert!(!mutex.contended());
+    ///
+    /// let guard = mutex.lock();
+    /// assert!(mutex.contended());
+    /// # drop(guard);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contended(&self) -> bool {
+        self.lock.is_locked()
+    }
+
+    /// Attempts to acquire this lock, spinning at most `spins` times before
+    /// giving up.
+    ///
+    /// This is meant for [`Drop`] impls and panic paths that want to clean
+    /// up if the lock is cheaply available, but must never deadlock, for
+    /// example because the current thread may already hold the lock.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{BestEffortGuard, Mutex};
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let guard = mutex.lock();
+    ///
+    /// assert!(matches!(mutex.lock_best_effort(100), BestEffortGuard::Unlocked(_)));
+    ///
+    /// drop(guard);
+    /// if let BestEffortGuard::Locked(mut guard) = mutex.lock_best_effort(100) {
+    ///     *guard = 1;
+    /// }
+    /// assert_eq!(*mutex.lock(), 1);
+    /// ```
+    pub fn lock_best_effort(&self, spins: u32) -> BestEffortGuard<'_, T, R> {
+        self.try_lock_spinning(spins)
+            .map_or(BestEffortGuard::Unlocked(self), BestEffortGuard::Locked)
+    }
+
+    /// Attempts to acquire this lock, spinning at most `spins` times before
+    /// giving up.
+    ///
+    /// This is [`lock_best_effort`] for callers that want to propagate the
+    /// failure with `?`.
+    ///
+    /// [`lock_best_effort`]: Self::lock_best_effort
+    ///
+    /// # Errors
+    /// Returns [`SpinBudgetExceeded`] if the lock is still held after
+    /// spinning `spins` times.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Mutex, SpinBudgetExceeded};
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let guard = mutex.lock();
+    ///
+    /// assert_eq!(mutex.try_lock_spinning(100).unwrap_err(), SpinBudgetExceeded { spins: 100 });
+    ///
+    /// drop(guard);
+    /// assert!(mutex.try_lock_spinning(100).is_ok());
+    /// ```
+    pub fn try_lock_spinning(
+        &self,
+        spins: u32,
+    ) -> Result<MutexGuard<'_, T, R>, SpinBudgetExceeded> {
+        for _ in 0..spins {
+            if let Some(guard) = self.try_lock() {
+                return Ok(guard);
+            }
+
+            core::hint::spin_loop();
+        }
+
+        self.try_lock().ok_or(SpinBudgetExceeded { spins })
+    }
+
+    /// Forcibly unlocks the mutex, regardless of who holds it.
+    ///
+    /// This is an escape hatch for recovering a mutex whose guard was leaked
+    /// or whose owner was killed while holding it. The protected data is left
+    /// as the previous owner last wrote it, which may be halfway through an
+    /// update.
+    ///
+    /// # Safety
+    /// No [`MutexGuard`] (or guard derived from one) for this mutex may be
+    /// used after this call. Resetting a mutex whose guard is still alive
+    /// allows two threads to access the data at once.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// core::mem::forget(mutex.lock());
+    /// assert!(mutex.try_lock().is_none());
+    ///
+    /// // SAFETY: The only guard was leaked and can never be used again.
+    /// unsafe { mutex.force_reset() };
+    /// assert!(mutex.try_lock().is_some());
+    /// ```
+    #[inline]
+    pub unsafe fn force_reset(&self) {
+        self.lock.unlock();
+    }
+
+    /// Returns whether a thread panicked while holding a guard of this mutex.
+    ///
+    /// Only a panic that starts while the guard is held poisons the mutex. A
+    /// guard taken by a thread that is already unwinding, for example in a
+    /// destructor, unlocks it normally.
+    ///
+    /// With the `abort_on_panic` feature, such a panic aborts the process
+    /// instead, so this always returns `false`.
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "abort_on_panic", doc = "```ignore")]
+    #[cfg_attr(not(feature = "abort_on_panic"), doc = "```")]
+    /// use skirt::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new(0);
+    ///
+    /// let _ = std::panic::catch_unwind(|| {
+    ///     let _guard = mutex.lock();
+    ///     panic!();
+    /// });
+    ///
+    /// assert!(mutex.is_poisoned());
+    /// mutex.clear_poison();
+    /// assert!(!mutex.is_poisoned());
+    ///
+    /// struct Cleanup<'a>(&'a Mutex<i32>);
+    ///
+    /// impl Drop for Cleanup<'_> {
+    ///     fn drop(&mut self) {
+    ///         *self.0.lock() += 1;
+    ///     }
+    /// }
+    ///
+    /// let _ = std::panic::catch_unwind(|| {
+    ///     let _cleanup = Cleanup(&mutex);
+    ///     panic!();
+    /// });
+    ///
+    /// assert_eq!(*mutex.lock(), 1);
+    /// assert!(!mutex.is_poisoned());
+    /// ```
+    #[cfg(feature = "poison")]
+    #[inline]
+    #[must_use]
+    pub fn is_poisoned(&self) -> bool {
+        self.lock.state.load(Ordering::Relaxed) & POISONED != 0
+    }
+
+    /// Clears the poisoned state of this mutex.
+    #[cfg(feature = "poison")]
+    #[inline]
+    pub fn clear_poison(&self) {
+        self.lock.state.fetch_and(!POISONED, Ordering::Relaxed);
+    }
+
+    /// Acquires the mutex, first repairing the data with `recover` and
+    /// clearing the poisoned state if the mutex is poisoned.
+    ///
+    /// If `recover` panics, the mutex stays poisoned.
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "abort_on_panic", doc = "```ignore")]
+    #[cfg_attr(not(feature = "abort_on_panic"), doc = "```")]
+    /// use skirt::sync::Mutex;
+    ///
+    /// let accounts = Mutex::new(vec![10, 20]);
+    ///
+    /// let _ = std::panic::catch_unwind(|| {
+    ///     let mut accounts = accounts.lock();
+    ///     accounts[0] -= 5;
+    ///     panic!("crashed before crediting the other account");
+    /// });
+    ///
+    /// let accounts = accounts.lock_or_recover(|accounts| accounts[0] += 5);
+    /// assert_eq!(*accounts, [10, 20]);
+    /// ```
+    #[cfg(feature = "poison")]
+    pub fn lock_or_recover<F>(&self, recover: F) -> MutexGuard<'_, T, R>
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut guard = self.lock();
+
+        if self.is_poisoned() {
+            recover(&mut guard);
+            self.clear_poison();
+        }
+
+        guard
+    }
+
+    /// Acquires the mutex, reporting whether it is poisoned like the
+    /// standard library's `Mutex::lock`.
+    ///
+    /// Without the `poison` feature a mutex is never poisoned, so this always
+    /// succeeds, and code written against it works with and without the
+    /// feature.
+    ///
+    /// # Errors
+    /// Returns the guard wrapped in a [`PoisonError`] if the mutex is
+    /// poisoned. The poison is not cleared.
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "abort_on_panic", doc = "```ignore")]
+    #[cfg_attr(not(feature = "abort_on_panic"), doc = "```")]
+    /// use skirt::sync::{Mutex, PoisonError};
+    /// use std::panic::{self, AssertUnwindSafe};
+    ///
+    /// let mutex = Mutex::new(0);
+    ///
+    /// let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     let _guard = mutex.lock_checked().unwrap();
+    ///     panic!();
+    /// }));
+    ///
+    /// // The data is still reachable through the error.
+    /// let guard = mutex.lock_checked().unwrap_or_else(PoisonError::into_inner);
+    /// assert_eq!(*guard, 0);
+    /// ```
+    pub fn lock_checked(&self) -> Result<MutexGuard<'_, T, R>, PoisonError<MutexGuard<'_, T, R>>> {
+        let guard = self.lock();
+
+        #[cfg(feature = "poison")]
+        if self.is_poisoned() {
+            return Err(PoisonError::new(guard));
+        }
+
+        Ok(guard)
+    }
+
+    /// Locks the mutex and runs `f` inside a [`std::thread::scope`], passing
+    /// it the scope and the locked data.
+    ///
+    /// Threads spawned on the scope may borrow the data, and the lock is
+    /// released only after every one of them has been joined. This avoids
+    /// having to send a guard to the spawned threads.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new([0; 4]);
+    ///
+    /// mutex.scope_lock(|scope, data| {
+    ///     for (i, slot) in data.iter_mut().enumerate() {
+    ///         scope.spawn(move || *slot = i * 2);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(*mutex.lock(), [0, 2, 4, 6]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn scope_lock<F, U>(&self, f: F) -> U
+    where
+        F: for<'scope, 'env> FnOnce(&'scope std::thread::Scope<'scope, 'env>, &'scope mut T) -> U,
+    {
+        let mut guard = self.lock();
+        std::thread::scope(|scope| f(scope, &mut *guard))
     }
 
     /// Consumes this mutex, returning the underlying data.
@@ -200,6 +993,44 @@ pub fn get_mut(&mut self) -> &mut T {
     }
 }
 
+impl<T, R: RelaxStrategy> Mutex<Option<T>, R> {
+    /// Locks the mutex, inserting the value computed by `f` if it is
+    /// [`None`], and returns a guard to the contained value.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new(None);
+    ///
+    /// *mutex.lock_insert_with(|| 1) += 1;
+    /// assert_eq!(*mutex.lock_insert_with(|| 10), 2);
+    /// ```
+    pub fn lock_insert_with<F>(&self, f: F) -> MappedMutexGuard<'_, T>
+    where
+        F: FnOnce() -> T,
+    {
+        MutexGuard::map(self.lock(), |data| data.get_or_insert_with(f))
+    }
+
+    /// Takes the value out of the mutex, leaving [`None`] in its place.
+    ///
+    /// The lock is only held for the duration of the call.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new(Some(5));
+    ///
+    /// assert_eq!(mutex.lock_take(), Some(5));
+    /// assert_eq!(mutex.lock_take(), None);
+    /// ```
+    pub fn lock_take(&self) -> Option<T> {
+        self.lock().take()
+    }
+}
+
 impl<T> From<T> for Mutex<T> {
     /// Creates a new mutex in an unlocked state ready for use.
     /// This is equivalent to [`Mutex::new`].
@@ -215,7 +1046,7 @@ fn default() -> Self {
     }
 }
 
-impl<T: ?Sized + Debug> Debug for Mutex<T> {
+impl<T: ?Sized + Debug, R: RelaxStrategy> Debug for Mutex<T, R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let mut d = f.debug_struct("Mutex");
 
@@ -239,27 +1070,177 @@ fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
 ///
 /// [`lock`]: Mutex::lock
 /// [`try_lock`]: Mutex::try_lock
-pub struct MutexGuard<'m, T: ?Sized> {
-    mutex: &'m Mutex<T>,
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this mutex while it is held"
+)]
+pub struct MutexGuard<'m, T: ?Sized, R: RelaxStrategy = Configured> {
+    pub(crate) mutex: &'m Mutex<T, R>,
+    acquired: Acquired,
     #[cfg(not(feature = "nightly"))]
     phantom: core::marker::PhantomData<*const ()>,
 }
 
 #[cfg(feature = "nightly")]
-impl<T: ?Sized> !Send for MutexGuard<'_, T> {}
-unsafe impl<T: ?Sized + Sync> Sync for MutexGuard<'_, T> {}
+impl<T: ?Sized, R: RelaxStrategy> !Send for MutexGuard<'_, T, R> {}
+unsafe impl<T: ?Sized + Sync, R: RelaxStrategy> Sync for MutexGuard<'_, T, R> {}
 
-impl<'m, T: ?Sized> MutexGuard<'m, T> {
-    const fn new(mutex: &'m Mutex<T>) -> Self {
+impl<'m, T: ?Sized, R: RelaxStrategy> MutexGuard<'m, T, R> {
+    const fn new(mutex: &'m Mutex<T, R>, acquired: Acquired) -> Self {
         Self {
             mutex,
+            acquired,
             #[cfg(not(feature = "nightly"))]
             phantom: core::marker::PhantomData,
         }
     }
+
+    /// Consumes the guard without unlocking the mutex, returning a raw pointer
+    /// to the locked mutex.
+    ///
+    /// The mutex stays locked until the pointer is passed back to [`from_raw`]
+    /// and the reconstructed guard is dropped. This allows a lock to be held
+    /// across an FFI boundary without keeping a guard value alive.
+    ///
+    /// [`from_raw`]: Self::from_raw
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let raw = MutexGuard::into_raw(mutex.lock());
+    /// assert!(mutex.try_lock().is_none());
+    ///
+    /// let mut guard = unsafe { MutexGuard::from_raw(raw) };
+    /// *guard = 10;
+    /// drop(guard);
+    /// assert_eq!(*mutex.lock(), 10);
+    /// ```
+    #[must_use = "the mutex stays locked until the pointer is passed to `from_raw`"]
+    pub const fn into_raw(this: Self) -> *const Mutex<T, R> {
+        let mutex = this.mutex;
+        core::mem::forget(this);
+        mutex
+    }
+
+    /// Reconstructs a guard from a pointer returned by [`into_raw`].
+    ///
+    /// [`into_raw`]: Self::into_raw
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by [`into_raw`], the mutex must still be
+    /// locked by that call, and the pointer must not be used to reconstruct
+    /// more than one guard. The mutex must outlive the returned guard.
+    pub const unsafe fn from_raw(ptr: *const Mutex<T, R>) -> Self {
+        // SAFETY: The caller guarantees `ptr` is a live, locked mutex.
+        Self::new(unsafe { &*ptr }, Acquired::UNKNOWN)
+    }
+
+    /// Registers `f` to run on the protected data just before the lock is
+    /// released.
+    ///
+    /// This is useful for work that must happen exactly once per critical
+    /// section, such as publishing a dirty flag, regardless of which path
+    /// the critical section leaves through.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new((0, false));
+    ///
+    /// {
+    ///     let mut guard = MutexGuard::on_unlock(mutex.lock(), |data| data.1 = true);
+    ///     guard.0 += 1;
+    ///     assert!(!guard.1);
+    /// }
+    ///
+    /// assert_eq!(*mutex.lock(), (1, true));
+    /// ```
+    pub const fn on_unlock<F>(this: Self, f: F) -> OnUnlockGuard<'m, T, F, R>
+    where
+        F: FnOnce(&mut T),
+    {
+        OnUnlockGuard {
+            guard: this,
+            hook: ManuallyDrop::new(f),
+        }
+    }
+
+    /// Consumes the guard, computing a value from the locked data with `f`
+    /// and releasing the lock before returning it.
+    ///
+    /// This keeps the critical section to exactly the closure, so the guard
+    /// cannot accidentally be held for longer.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new(vec![1, 2, 3]);
+    ///
+    /// let len = MutexGuard::map_into(mutex.lock(), |data| {
+    ///     data.push(4);
+    ///     data.len()
+    /// });
+    ///
+    /// assert_eq!(len, 4);
+    /// assert!(mutex.try_lock().is_some());
+    /// ```
+    pub fn map_into<U, F>(mut this: Self, f: F) -> U
+    where
+        F: FnOnce(&mut T) -> U,
+    {
+        f(&mut this)
+    }
+
+    /// Makes a [`MappedMutexGuard`] for a component of the locked data.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new((0, 'a'));
+    ///
+    /// *MutexGuard::map(mutex.lock(), |data| &mut data.1) = 'b';
+    /// assert_eq!(*mutex.lock(), (0, 'b'));
+    /// ```
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedMutexGuard<'m, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let (mutex, acquired) = (this.mutex, this.acquired);
+        core::mem::forget(this);
+
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        let data = NonNull::from(f(unsafe { &mut *mutex.data.get() }));
+        MappedMutexGuard::new(&mutex.lock, acquired, data)
+    }
+
+    /// Attempts to make a [`MappedMutexGuard`] for a component of the locked
+    /// data, handing back the original guard if `f` returns [`None`].
+    ///
+    /// # Errors
+    /// Returns the original guard if `f` returns [`None`].
+    pub fn try_map<U: ?Sized, F>(this: Self, f: F) -> Result<MappedMutexGuard<'m, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        match f(unsafe { &mut *this.mutex.data.get() }) {
+            Some(data) => {
+                let data = NonNull::from(data);
+                let (mutex, acquired) = (this.mutex, this.acquired);
+                core::mem::forget(this);
+                Ok(MappedMutexGuard::new(&mutex.lock, acquired, data))
+            }
+            None => Err(this),
+        }
+    }
 }
 
-impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+impl<T: ?Sized, R: RelaxStrategy> Deref for MutexGuard<'_, T, R> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -268,35 +1249,187 @@ fn deref(&self) -> &Self::Target {
     }
 }
 
-impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+impl<T: ?Sized, R: RelaxStrategy> DerefMut for MutexGuard<'_, T, R> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         // SAFETY: The lock is held, giving us exclusive access to the data.
         unsafe { &mut *self.mutex.data.get() }
     }
 }
 
-impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+impl<T: ?Sized, R: RelaxStrategy> Drop for MutexGuard<'_, T, R> {
     #[inline]
     fn drop(&mut self) {
-        self.mutex.lock.store(false, Ordering::Release);
+        self.mutex.lock.release(self.acquired);
     }
 }
 
-impl<T: ?Sized + Debug> Debug for MutexGuard<'_, T> {
+impl<T: ?Sized + Debug, R: RelaxStrategy> Debug for MutexGuard<'_, T, R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         (**self).fmt(f)
     }
 }
 
-impl<T: ?Sized + Display> Display for MutexGuard<'_, T> {
+impl<T: ?Sized + Display, R: RelaxStrategy> Display for MutexGuard<'_, T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// The result of [`Mutex::lock_best_effort`].
+#[derive(Debug)]
+pub enum BestEffortGuard<'m, T: ?Sized, R: RelaxStrategy = Configured> {
+    /// The lock was acquired.
+    Locked(MutexGuard<'m, T, R>),
+    /// The lock was still held by someone else after the spin budget ran
+    /// out.
+    Unlocked(&'m Mutex<T, R>),
+}
+
+impl<'m, T: ?Sized, R: RelaxStrategy> BestEffortGuard<'m, T, R> {
+    /// Returns the guard if the lock was acquired.
+    #[must_use]
+    pub fn locked(self) -> Option<MutexGuard<'m, T, R>> {
+        match self {
+            Self::Locked(guard) => Some(guard),
+            Self::Unlocked(_) => None,
+        }
+    }
+}
+
+/// An RAII mutex guard returned by [`MutexGuard::map`], which can point to a
+/// component of the protected data. The mutex is unlocked when this guard is
+/// dropped.
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this mutex while it is held"
+)]
+pub struct MappedMutexGuard<'m, T: ?Sized> {
+    lock: &'m State,
+    acquired: Acquired,
+    data: NonNull<T>,
+    phantom: core::marker::PhantomData<(&'m mut T, *const ())>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for MappedMutexGuard<'_, T> {}
+
+impl<'m, T: ?Sized> MappedMutexGuard<'m, T> {
+    const fn new(lock: &'m State, acquired: Acquired, data: NonNull<T>) -> Self {
+        Self {
+            lock,
+            acquired,
+            data,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Makes a [`MappedMutexGuard`] for a further component of the data.
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedMutexGuard<'m, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let (lock, acquired, mut data) = (this.lock, this.acquired, this.data);
+        core::mem::forget(this);
+
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        let data = NonNull::from(f(unsafe { data.as_mut() }));
+        MappedMutexGuard::new(lock, acquired, data)
+    }
+}
+
+impl<T: ?Sized> Deref for MappedMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MappedMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        unsafe { self.data.as_mut() }
+    }
+}
+
+impl<T: ?Sized> Drop for MappedMutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.lock.release(self.acquired);
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for MappedMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for MappedMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A [`MutexGuard`] that runs a hook on the protected data just before the
+/// lock is released.
+///
+/// This structure is created by the [`on_unlock`] method on [`MutexGuard`].
+///
+/// [`on_unlock`]: MutexGuard::on_unlock
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this mutex while it is held"
+)]
+pub struct OnUnlockGuard<'m, T: ?Sized, F: FnOnce(&mut T), R: RelaxStrategy = Configured> {
+    guard: MutexGuard<'m, T, R>,
+    hook: ManuallyDrop<F>,
+}
+
+impl<T: ?Sized, F: FnOnce(&mut T), R: RelaxStrategy> Deref for OnUnlockGuard<'_, T, F, R> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<T: ?Sized, F: FnOnce(&mut T), R: RelaxStrategy> DerefMut for OnUnlockGuard<'_, T, F, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<T: ?Sized, F: FnOnce(&mut T), R: RelaxStrategy> Drop for OnUnlockGuard<'_, T, F, R> {
+    fn drop(&mut self) {
+        // SAFETY: The hook is taken exactly once, here.
+        let hook = unsafe { ManuallyDrop::take(&mut self.hook) };
+        hook(&mut self.guard);
+        // The inner guard is dropped afterwards, releasing the lock.
+    }
+}
+
+impl<T: ?Sized + Debug, F: FnOnce(&mut T), R: RelaxStrategy> Debug for OnUnlockGuard<'_, T, F, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display, F: FnOnce(&mut T), R: RelaxStrategy> Display
+    for OnUnlockGuard<'_, T, F, R>
+{
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         (**self).fmt(f)
     }
 }
 
 #[cfg(feature = "lock_api")]
-unsafe impl lock_api::RawMutex for Mutex<()> {
-    const INIT: Self = Self::new(());
+unsafe impl<R: RelaxStrategy> lock_api::RawMutex for Mutex<(), R> {
+    const INIT: Self = Self {
+        lock: State::new(),
+        strategy: PhantomData,
+        data: UnsafeCell::new(()),
+    };
 
     type GuardMarker = lock_api::GuardSend;
 
@@ -309,6 +1442,38 @@ fn try_lock(&self) -> bool {
     }
 
     unsafe fn unlock(&self) {
-        self.lock.store(false, Ordering::Release);
+        // `lock_api` keeps no state in its guards to remember whether the
+        // thread was panicking when it locked.
+        self.lock.release(Acquired::UNKNOWN);
+    }
+}
+
+/// Generates a mutex around an arbitrary value, in an arbitrary state.
+///
+/// The mutex may be generated locked, as if its guard had been leaked, and
+/// with the `poison` feature it may be generated poisoned. Code under test
+/// that calls [`Mutex::lock`] on a locked mutex spins forever, so property
+/// tests should reach generated mutexes through [`Mutex::try_lock`], or
+/// recover them with [`Mutex::force_reset`].
+#[cfg(feature = "arbitrary")]
+impl<'a, T: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for Mutex<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mutex = Self::new(T::arbitrary(u)?);
+
+        if u.arbitrary()? {
+            mutex.lock.state.fetch_or(LOCKED, Ordering::Relaxed);
+        }
+
+        #[cfg(feature = "poison")]
+        if u.arbitrary()? {
+            mutex.lock.state.fetch_or(POISONED, Ordering::Relaxed);
+        }
+
+        Ok(mutex)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let state = if cfg!(feature = "poison") { 2 } else { 1 };
+        arbitrary::size_hint::and(T::size_hint(depth), (state, Some(state)))
     }
 }
@@ -1,3 +1,4 @@
+use crate::profile::Backoff;
 use crate::sync::atomic::{AtomicU8, Ordering};
 use core::fmt::{Debug, Formatter};
 
@@ -16,6 +17,19 @@ impl Once {
     const RUNNING: u8 = 1;
     const COMPLETE: u8 = 2;
 
+    /// A `Once` that has not run yet, usable where a constant is needed, such
+    /// as in array repeat expressions.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::Once;
+    ///
+    /// static STAGES: [Once; 4] = [Once::INIT; 4];
+    /// assert!(!STAGES[0].is_completed());
+    /// ```
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const INIT: Self = Self::new();
+
     /// Creates a new `Once` value.
     #[inline]
     #[must_use]
@@ -27,16 +41,41 @@ pub const fn new() -> Self {
 
     /// Performs an initialization routine once and only once.
     /// The given closure will be executed if this is the first time `call_once` has been called, and otherwise the routine will not be invoked.
+    #[inline]
     pub fn call_once<F>(&self, f: F)
     where
         F: FnOnce(),
     {
+        // Keep the already-completed case to a single inlined load.
         if self.is_completed() {
             return;
         }
 
-        if self
-            .state
+        self.call_once_slow(f);
+    }
+
+    #[cold]
+    fn call_once_slow<F>(&self, f: F)
+    where
+        F: FnOnce(),
+    {
+        loop {
+            if self.try_begin() {
+                f();
+                self.finish();
+                return;
+            }
+
+            if self.wait() {
+                return;
+            }
+        }
+    }
+
+    /// Attempts to move from incomplete to running, returning whether this
+    /// caller is now responsible for finishing or aborting the routine.
+    pub(crate) fn try_begin(&self) -> bool {
+        self.state
             .compare_exchange(
                 Self::INCOMPLETE,
                 Self::RUNNING,
@@ -44,17 +83,27 @@ pub fn call_once<F>(&self, f: F)
                 Ordering::Acquire,
             )
             .is_ok()
-        {
-            f();
+    }
+
+    /// Marks a routine started with [`try_begin`](Self::try_begin) as complete.
+    pub(crate) fn finish(&self) {
+        self.state.store(Self::COMPLETE, Ordering::Release);
+    }
 
-            self.state.store(Self::COMPLETE, Ordering::Release);
-        } else {
-            while self.state.load(Ordering::Acquire) == Self::RUNNING {
-                #[cfg(not(feature = "std"))]
-                core::hint::spin_loop();
+    /// Returns a routine started with [`try_begin`](Self::try_begin) to the
+    /// incomplete state so another caller can retry it.
+    pub(crate) fn abort(&self) {
+        self.state.store(Self::INCOMPLETE, Ordering::Release);
+    }
+
+    /// Waits while a routine is running, returning whether it completed.
+    pub(crate) fn wait(&self) -> bool {
+        let mut backoff = Backoff::new();
 
-                #[cfg(feature = "std")]
-                std::thread::yield_now();
+        loop {
+            match self.state.load(Ordering::Acquire) {
+                Self::RUNNING => backoff.relax(),
+                state => return state == Self::COMPLETE,
             }
         }
     }
@@ -78,6 +127,7 @@ pub fn call_once<F>(&self, f: F)
     ///
     /// assert_eq!(INIT.is_completed(), true);
     /// ```
+    #[inline]
     pub fn is_completed(&self) -> bool {
         self.state.load(Ordering::Acquire) == Self::COMPLETE
     }
@@ -0,0 +1,108 @@
+use crate::once::Once;
+use crate::profile::Backoff;
+use core::fmt::{Debug, Formatter};
+
+/// A group of `N` independent initialization steps, each run only once.
+///
+/// Each step is completed with [`call_once`], possibly from different
+/// threads, and [`wait_all`] blocks until every step is done. This replaces
+/// an array of [`Once`] values plus manual polling, such as when bringing up
+/// several drivers in parallel.
+///
+/// [`call_once`]: Self::call_once
+/// [`wait_all`]: Self::wait_all
+///
+/// # Examples
+/// ```
+/// use skirt::sync::OnceGroup;
+/// use std::thread;
+///
+/// static BRING_UP: OnceGroup<3> = OnceGroup::new();
+///
+/// let handles: Vec<_> = (0..3)
+///     .map(|step| thread::spawn(move || BRING_UP.call_once(step, || {})))
+///     .collect();
+///
+/// BRING_UP.wait_all();
+/// assert!(BRING_UP.is_all_completed());
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// ```
+pub struct OnceGroup<const N: usize> {
+    steps: [Once; N],
+}
+
+impl<const N: usize> OnceGroup<N> {
+    /// Creates a new group with no step completed.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            steps: [const { Once::new() }; N],
+        }
+    }
+
+    /// Runs `f` as step `index` if that step has not been completed yet.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    pub fn call_once<F>(&self, index: usize, f: F)
+    where
+        F: FnOnce(),
+    {
+        self.steps[index].call_once(f);
+    }
+
+    /// Returns whether step `index` has been completed.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    #[must_use]
+    pub fn is_completed(&self, index: usize) -> bool {
+        self.steps[index].is_completed()
+    }
+
+    /// Returns whether every step has been completed.
+    #[must_use]
+    pub fn is_all_completed(&self) -> bool {
+        self.steps.iter().all(Once::is_completed)
+    }
+
+    /// Blocks the current thread until step `index` has been completed.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    pub fn wait(&self, index: usize) {
+        let mut backoff = Backoff::new();
+
+        while !self.steps[index].wait() {
+            backoff.relax();
+        }
+    }
+
+    /// Blocks the current thread until every step has been completed.
+    pub fn wait_all(&self) {
+        for index in 0..N {
+            self.wait(index);
+        }
+    }
+}
+
+impl<const N: usize> Default for OnceGroup<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Debug for OnceGroup<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OnceGroup")
+            .field(
+                "completed",
+                &self.steps.iter().filter(|step| step.is_completed()).count(),
+            )
+            .finish_non_exhaustive()
+    }
+}
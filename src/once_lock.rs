@@ -12,6 +12,11 @@ unsafe impl<T: Sync + Send> Sync for OnceLock<T> {}
 unsafe impl<T: Send> Send for OnceLock<T> {}
 
 impl<T> OnceLock<T> {
+    /// An empty cell, usable where a constant is needed, such as in array
+    /// repeat expressions.
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const INIT: Self = Self::new();
+
     #[inline]
     #[must_use]
     pub const fn new() -> Self {
@@ -51,17 +56,227 @@ pub fn get_or_init<F>(&self, f: F) -> &T
         unsafe { self.get_unchecked() }
     }
 
+    /// Gets the contents of the cell, initializing it in place with `f` if
+    /// the cell was empty.
+    ///
+    /// `f` writes the value directly into the cell's storage, so large values
+    /// such as frame buffers or lookup tables are never built on the stack
+    /// and then moved.
+    ///
+    /// # Safety
+    /// `f` must fully initialize the value it is given before returning.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::OnceLock;
+    ///
+    /// static TABLE: OnceLock<[u32; 1024]> = OnceLock::new();
+    ///
+    /// // SAFETY: Every element is written before the closure returns.
+    /// let table = unsafe {
+    ///     TABLE.get_or_init_in_place(|slot| {
+    ///         let base = slot.as_mut_ptr().cast::<u32>();
+    ///         for i in 0..1024 {
+    ///             base.add(i).write(i as u32 * 2);
+    ///         }
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(table[512], 1024);
+    /// ```
+    pub unsafe fn get_or_init_in_place<F>(&self, f: F) -> &T
+    where
+        F: FnOnce(&mut MaybeUninit<T>),
+    {
+        if !self.is_initialized() {
+            let slot = &self.data;
+            self.once.call_once(|| f(unsafe { &mut *slot.get() }));
+        }
+
+        unsafe { self.get_unchecked() }
+    }
+
+    /// Gets the contents of the cell, initializing it with `f` if the cell
+    /// was empty, without serializing the initializers.
+    ///
+    /// Several threads may run `f` concurrently. The first to finish stores
+    /// its value, and the others drop theirs and return the stored one. A
+    /// thread never waits for another's `f`, only for the store of the
+    /// winning value, which suits values that are cheap to compute.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::OnceLock;
+    ///
+    /// static PAGE_SIZE: OnceLock<usize> = OnceLock::new();
+    ///
+    /// assert_eq!(*PAGE_SIZE.get_or_init_racy(|| 4096), 4096);
+    /// assert_eq!(*PAGE_SIZE.get_or_init_racy(|| 8192), 4096);
+    /// ```
+    pub fn get_or_init_racy<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        if let Some(data) = self.get() {
+            return data;
+        }
+
+        let data = f();
+
+        loop {
+            if self.once.try_begin() {
+                unsafe { (*self.data.get()).write(data) };
+                self.once.finish();
+                break;
+            }
+
+            if self.once.wait() {
+                break;
+            }
+        }
+
+        unsafe { self.get_unchecked() }
+    }
+
+    /// Gets the contents of the cell, initializing it with `f` if the cell
+    /// was empty, and recovering if `f` panics.
+    ///
+    /// A panic in `f` is caught and the cell is returned to the empty state,
+    /// so a later call can retry the initialization instead of the cell
+    /// being stuck forever. Threads that were waiting on the failed attempt
+    /// retry it themselves.
+    ///
+    /// # Errors
+    /// Returns the panic payload if `f` panicked during this call.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::OnceLock;
+    ///
+    /// static CONFIG: OnceLock<u32> = OnceLock::new();
+    ///
+    /// assert!(CONFIG.get_or_init_recover(|| panic!("not ready")).is_err());
+    /// assert_eq!(CONFIG.get(), None);
+    ///
+    /// assert_eq!(CONFIG.get_or_init_recover(|| 7).ok(), Some(&7));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn get_or_init_recover<F>(&self, f: F) -> std::thread::Result<&T>
+    where
+        F: FnOnce() -> T,
+    {
+        if let Some(data) = self.get() {
+            return Ok(data);
+        }
+
+        let mut f = Some(f);
+
+        loop {
+            if self.once.try_begin() {
+                let Some(f) = f.take() else { unreachable!() };
+
+                match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+                    Ok(data) => {
+                        unsafe { (*self.data.get()).write(data) };
+                        self.once.finish();
+                        break;
+                    }
+                    Err(payload) => {
+                        self.once.abort();
+                        return Err(payload);
+                    }
+                }
+            }
+
+            if self.once.wait() {
+                break;
+            }
+        }
+
+        Ok(unsafe { self.get_unchecked() })
+    }
+
+    /// Gets the contents of the cell, initializing it from the value of
+    /// `other` with `f` if the cell was empty.
+    ///
+    /// Returns [`None`] if both cells are empty. Like [`get_or_init`], `f` runs
+    /// at most once, even if several threads race to initialize the cell.
+    ///
+    /// [`get_or_init`]: Self::get_or_init
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::OnceLock;
+    ///
+    /// static BASE: OnceLock<u32> = OnceLock::new();
+    /// static DERIVED: OnceLock<u32> = OnceLock::new();
+    ///
+    /// assert_eq!(DERIVED.get_or_init_from(&BASE, |base| base * 2), None);
+    ///
+    /// BASE.set(21).unwrap();
+    /// assert_eq!(DERIVED.get_or_init_from(&BASE, |base| base * 2), Some(&42));
+    /// ```
     #[inline]
+    pub fn get_or_init_from<U, F>(&self, other: &OnceLock<U>, f: F) -> Option<&T>
+    where
+        F: FnOnce(&U) -> T,
+    {
+        if let Some(data) = self.get() {
+            return Some(data);
+        }
+
+        let source = other.get()?;
+        Some(self.get_or_init(|| f(source)))
+    }
+
+    /// Initializes the cell with `data` if it is empty.
     ///
     /// # Errors
+    /// Returns `data` back if the cell was already initialized, including
+    /// when another thread initializes it concurrently and wins the race.
+    /// Exactly one of the racing calls succeeds.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::OnceLock;
+    ///
+    /// static CELL: OnceLock<usize> = OnceLock::new();
+    ///
+    /// let results: Vec<_> = std::thread::scope(|s| {
+    ///     let handles: Vec<_> = (0..4).map(|i| s.spawn(move || CELL.set(i))).collect();
+    ///     handles.into_iter().map(|h| h.join().unwrap()).collect()
+    /// });
+    ///
+    /// // The winner's value is stored, and every loser gets its own back.
+    /// let winner = *CELL.get().unwrap();
+    ///
+    /// for (i, result) in results.into_iter().enumerate() {
+    ///     if i == winner {
+    ///         assert_eq!(result, Ok(()));
+    ///     } else {
+    ///         assert_eq!(result, Err(i));
+    ///     }
+    /// }
+    /// ```
+    #[inline]
     pub fn set(&self, data: T) -> Result<(), T> {
         if self.is_initialized() {
             return Err(data);
         }
 
-        self.initialize(|| data);
+        // Another thread may win the race to initialize, in which case our
+        // closure never runs and the value is handed back.
+        let mut data = Some(data);
+        let slot = &self.data;
+        self.once.call_once(|| {
+            if let Some(data) = data.take() {
+                // SAFETY: `call_once` runs at most one closure, which has
+                // exclusive access to the uninitialized slot.
+                unsafe { (*slot.get()).write(data) };
+            }
+        });
 
-        Ok(())
+        data.map_or(Ok(()), Err)
     }
 
     #[inline]
@@ -175,3 +390,33 @@ fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         d.finish()
     }
 }
+
+/// Evaluates an expression once per callsite and returns a `&'static`
+/// reference to the result.
+///
+/// This expands to a hidden `static` [`OnceLock`] initialized with the
+/// expression the first time the callsite is reached, making it cheap to
+/// memoize a computation inside a hot function without declaring a
+/// module-level static. Because statics need an explicit type, the type of
+/// the value is given before the expression.
+///
+/// [`OnceLock`]: crate::sync::OnceLock
+///
+/// # Examples
+/// ```
+/// use skirt::memo;
+///
+/// fn table() -> &'static [u32; 4] {
+///     memo!([u32; 4] = core::array::from_fn(|i| 1 << i))
+/// }
+///
+/// assert_eq!(table(), &[1, 2, 4, 8]);
+/// assert!(core::ptr::eq(table(), table()));
+/// ```
+#[macro_export]
+macro_rules! memo {
+    ($ty:ty = $init:expr) => {{
+        static MEMO: $crate::sync::OnceLock<$ty> = $crate::sync::OnceLock::new();
+        MEMO.get_or_init(|| $init)
+    }};
+}
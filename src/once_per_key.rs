@@ -0,0 +1,100 @@
+use crate::mutex::Mutex;
+use crate::once::Once;
+use core::fmt::{Debug, Formatter};
+
+/// A set of [`Once`]s indexed by key, running a routine at most once per
+/// distinct key.
+///
+/// Up to `N` distinct keys can be tracked, each taking a slot for the
+/// lifetime of the set. Looking up a key's slot takes a short lock, while
+/// the routines themselves run outside of it, so routines for different keys
+/// run concurrently. This suits one-time setup per connection or per device.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::OncePerKey;
+///
+/// static SETUP: OncePerKey<u8, 4> = OncePerKey::new();
+///
+/// let mut runs = 0;
+/// SETUP.call_once(1, || runs += 1).unwrap();
+/// SETUP.call_once(1, || runs += 1).unwrap();
+/// SETUP.call_once(2, || runs += 1).unwrap();
+///
+/// assert_eq!(runs, 2);
+/// assert!(SETUP.is_completed(&1));
+/// assert!(!SETUP.is_completed(&3));
+/// ```
+pub struct OncePerKey<K, const N: usize> {
+    keys: Mutex<[Option<K>; N]>,
+    onces: [Once; N],
+}
+
+impl<K: Eq, const N: usize> OncePerKey<K, N> {
+    /// Creates a new set with no key seen yet.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            keys: Mutex::new([const { None }; N]),
+            onces: [const { Once::new() }; N],
+        }
+    }
+
+    /// Runs `f` if no routine has been completed for `key` yet.
+    ///
+    /// If another thread is running the routine for `key`, this blocks until
+    /// it finishes.
+    ///
+    /// # Errors
+    /// Returns `key` back if it has not been seen before and all `N` slots
+    /// are taken, in which case `f` is not run.
+    pub fn call_once<F>(&self, key: K, f: F) -> Result<(), K>
+    where
+        F: FnOnce(),
+    {
+        let index = {
+            let mut keys = self.keys.lock();
+
+            match keys.iter().position(|slot| slot.as_ref() == Some(&key)) {
+                Some(index) => index,
+                None => match keys.iter().position(Option::is_none) {
+                    Some(index) => {
+                        keys[index] = Some(key);
+                        index
+                    }
+                    None => return Err(key),
+                },
+            }
+        };
+
+        self.onces[index].call_once(f);
+        Ok(())
+    }
+
+    /// Returns whether a routine has been completed for `key`.
+    pub fn is_completed(&self, key: &K) -> bool {
+        self.keys
+            .lock()
+            .iter()
+            .position(|slot| slot.as_ref() == Some(key))
+            .is_some_and(|index| self.onces[index].is_completed())
+    }
+}
+
+impl<K: Eq, const N: usize> Default for OncePerKey<K, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, const N: usize> Debug for OncePerKey<K, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OncePerKey")
+            .field(
+                "completed",
+                &self.onces.iter().filter(|once| once.is_completed()).count(),
+            )
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,81 @@
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A gate that opens at most once per period, across all threads.
+///
+/// [`try_run`] is given the current time in ticks of any monotonic clock
+/// (milliseconds, timer counts, ...) and returns `true` to exactly one caller
+/// per period. It never locks, which makes it suitable for throttling log
+/// messages or feeding a watchdog from several threads or interrupt handlers.
+///
+/// Ticks are compared with wrapping arithmetic, so a clock that wraps around
+/// is handled as long as calls are less than `usize::MAX` ticks apart.
+///
+/// [`try_run`]: Self::try_run
+///
+/// # Examples
+/// ```
+/// use skirt::sync::Periodic;
+///
+/// static LOG_THROTTLE: Periodic = Periodic::new(1000);
+///
+/// assert!(LOG_THROTTLE.try_run(5));
+/// assert!(!LOG_THROTTLE.try_run(500));
+/// assert!(!LOG_THROTTLE.try_run(1004));
+/// assert!(LOG_THROTTLE.try_run(1005));
+/// ```
+pub struct Periodic {
+    period: usize,
+    last: AtomicUsize,
+}
+
+impl Periodic {
+    /// Creates a new gate that opens at most once every `period` ticks.
+    ///
+    /// The gate starts out open.
+    #[inline]
+    #[must_use]
+    pub const fn new(period: usize) -> Self {
+        Self {
+            period,
+            // Pretend the last run was one period before tick zero, so the
+            // first call is let through.
+            last: AtomicUsize::new(period.wrapping_neg()),
+        }
+    }
+
+    /// Returns `true` if at least a period has passed since the gate last
+    /// opened, and this call is the one to open it.
+    pub fn try_run(&self, now: usize) -> bool {
+        let last = self.last.load(Ordering::Relaxed);
+
+        now.wrapping_sub(last) >= self.period
+            && self
+                .last
+                .compare_exchange(last, now, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+    }
+
+    /// Runs `f` if the gate opens at `now`, returning its result.
+    pub fn run<F, R>(&self, now: usize, f: F) -> Option<R>
+    where
+        F: FnOnce() -> R,
+    {
+        self.try_run(now).then(f)
+    }
+
+    /// Returns the period of this gate, in ticks.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Debug for Periodic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Periodic")
+            .field("period", &self.period)
+            .field("last", &self.last.load(Ordering::Relaxed))
+            .finish()
+    }
+}
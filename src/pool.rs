@@ -0,0 +1,155 @@
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+
+/// A fixed-size pool of reusable objects.
+///
+/// [`get`] hands out exclusive access to one of the `N` objects through a
+/// [`PoolGuard`], and blocks while every object is in use. Dropping the guard
+/// returns the object to the pool as-is, which makes this a convenient way to
+/// reuse buffers without an allocator.
+///
+/// [`get`]: Self::get
+///
+/// # Examples
+/// ```
+/// use skirt::sync::Pool;
+///
+/// static BUFFERS: Pool<[u8; 64], 2> = Pool::new([[0; 64]; 2]);
+///
+/// let mut a = BUFFERS.get();
+/// let b = BUFFERS.get();
+/// a[0] = 1;
+///
+/// assert!(BUFFERS.try_get().is_none());
+/// drop(b);
+/// assert!(BUFFERS.try_get().is_some());
+/// ```
+pub struct Pool<T, const N: usize> {
+    used: [AtomicBool; N],
+    items: UnsafeCell<[T; N]>,
+}
+
+unsafe impl<T: Send, const N: usize> Send for Pool<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for Pool<T, N> {}
+
+impl<T, const N: usize> Pool<T, N> {
+    /// Creates a new pool holding `items`, all of which are available.
+    #[inline]
+    pub const fn new(items: [T; N]) -> Self {
+        Self {
+            used: [const { AtomicBool::new(false) }; N],
+            items: UnsafeCell::new(items),
+        }
+    }
+
+    /// Takes an object out of the pool, blocking the current thread until
+    /// one is available.
+    pub fn get(&self) -> PoolGuard<'_, T, N> {
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Some(guard) = self.try_get() {
+                return guard;
+            }
+
+            backoff.relax();
+        }
+    }
+
+    /// Attempts to take an object out of the pool without blocking.
+    #[must_use]
+    pub fn try_get(&self) -> Option<PoolGuard<'_, T, N>> {
+        self.used.iter().enumerate().find_map(|(index, used)| {
+            used.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+                .then(|| PoolGuard { pool: self, index })
+        })
+    }
+
+    /// Returns the number of objects currently available.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.used
+            .iter()
+            .filter(|used| !used.load(Ordering::Relaxed))
+            .count()
+    }
+
+    /// Consumes this pool, returning the objects it holds.
+    pub fn into_inner(self) -> [T; N] {
+        self.items.into_inner()
+    }
+}
+
+impl<T: Default, const N: usize> Default for Pool<T, N> {
+    fn default() -> Self {
+        Self::new(core::array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> Debug for Pool<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Pool")
+            .field("available", &self.available())
+            .finish_non_exhaustive()
+    }
+}
+
+/// RAII structure giving exclusive access to an object of a [`Pool`], which
+/// is returned to the pool when dropped.
+///
+/// This structure is created by the [`get`] and [`try_get`] methods on
+/// [`Pool`].
+///
+/// [`get`]: Pool::get
+/// [`try_get`]: Pool::try_get
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks wait for this object while it is held"
+)]
+pub struct PoolGuard<'p, T, const N: usize> {
+    pool: &'p Pool<T, N>,
+    index: usize,
+}
+
+unsafe impl<T: Sync, const N: usize> Sync for PoolGuard<'_, T, N> {}
+
+impl<T, const N: usize> Deref for PoolGuard<'_, T, N> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The slot is marked as used, giving us exclusive access.
+        unsafe { &*self.pool.items.get().cast::<T>().add(self.index) }
+    }
+}
+
+impl<T, const N: usize> DerefMut for PoolGuard<'_, T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The slot is marked as used, giving us exclusive access.
+        unsafe { &mut *self.pool.items.get().cast::<T>().add(self.index) }
+    }
+}
+
+impl<T, const N: usize> Drop for PoolGuard<'_, T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.pool.used[self.index].store(false, Ordering::Release);
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for PoolGuard<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Display, const N: usize> Display for PoolGuard<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
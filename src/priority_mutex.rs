@@ -0,0 +1,225 @@
+use crate::context::current_priority;
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+
+const MAX_PRIORITY: u8 = 31;
+const LEVELS: usize = MAX_PRIORITY as usize + 1;
+const NO_HANDOFF: u8 = u8::MAX;
+
+/// A mutual exclusion primitive that hands the lock to the most urgent waiter.
+///
+/// Waiters register with a priority, and when the lock is released while
+/// others are waiting it is handed directly to a waiter of the highest
+/// waiting priority instead of being left up for grabs. This bounds the time
+/// a high-priority context can be kept waiting by lower-priority ones.
+///
+/// [`lock`] uses the priority reported by the registered
+/// [`CurrentContext`], while [`lock_with_priority`] takes it explicitly.
+/// Priorities above [`MAX_PRIORITY`] are treated as [`MAX_PRIORITY`].
+///
+/// [`lock`]: Self::lock
+/// [`lock_with_priority`]: Self::lock_with_priority
+/// [`MAX_PRIORITY`]: Self::MAX_PRIORITY
+/// [`CurrentContext`]: crate::sync::CurrentContext
+///
+/// # Examples
+/// ```
+/// use skirt::sync::PriorityMutex;
+///
+/// let mutex = PriorityMutex::new(0);
+///
+/// *mutex.lock_with_priority(7) += 1;
+/// assert_eq!(*mutex.lock(), 1);
+/// ```
+pub struct PriorityMutex<T: ?Sized> {
+    lock: AtomicBool,
+    handoff: AtomicU8,
+    waiting: [AtomicUsize; LEVELS],
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for PriorityMutex<T> {}
+unsafe impl<T: ?Sized + Send> Sync for PriorityMutex<T> {}
+
+impl<T> PriorityMutex<T> {
+    /// The highest distinct priority.
+    pub const MAX_PRIORITY: u8 = MAX_PRIORITY;
+
+    /// Creates a new mutex in an unlocked state ready for use.
+    #[inline]
+    pub const fn new(data: T) -> Self {
+        Self {
+            lock: AtomicBool::new(false),
+            handoff: AtomicU8::new(NO_HANDOFF),
+            waiting: [const { AtomicUsize::new(0) }; LEVELS],
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consumes this mutex, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> PriorityMutex<T> {
+    /// Acquires the mutex with the priority of the executing context,
+    /// blocking until it is able to do so.
+    pub fn lock(&self) -> PriorityMutexGuard<'_, T> {
+        self.lock_with_priority(current_priority())
+    }
+
+    /// Acquires the mutex with the given priority, blocking until it is able
+    /// to do so.
+    pub fn lock_with_priority(&self, priority: u8) -> PriorityMutexGuard<'_, T> {
+        if let Some(guard) = self.try_lock() {
+            return guard;
+        }
+
+        let priority = priority.min(MAX_PRIORITY);
+        let waiting = &self.waiting[priority as usize];
+        waiting.fetch_add(1, Ordering::AcqRel);
+
+        let mut backoff = Backoff::new();
+
+        loop {
+            // The lock is either handed to our priority level directly, or was
+            // released while nobody was waiting.
+            if self
+                .handoff
+                .compare_exchange(priority, NO_HANDOFF, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+                || self
+                    .lock
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                break;
+            }
+
+            backoff.relax();
+        }
+
+        waiting.fetch_sub(1, Ordering::AcqRel);
+        PriorityMutexGuard { mutex: self }
+    }
+
+    /// Attempts to acquire the mutex without blocking.
+    ///
+    /// This fails if the mutex is locked or if another context is waiting
+    /// for it.
+    #[must_use]
+    pub fn try_lock(&self) -> Option<PriorityMutexGuard<'_, T>> {
+        (self.highest_waiting().is_none()
+            && self
+                .lock
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok())
+        .then(|| PriorityMutexGuard { mutex: self })
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `PriorityMutex` mutably, no actual locking
+    /// needs to take place.
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    fn highest_waiting(&self) -> Option<u8> {
+        (0..=MAX_PRIORITY)
+            .rev()
+            .find(|&priority| self.waiting[priority as usize].load(Ordering::Acquire) > 0)
+    }
+
+    fn unlock(&self) {
+        match self.highest_waiting() {
+            // Keep the lock held and pass ownership to the waiting level.
+            Some(priority) => self.handoff.store(priority, Ordering::Release),
+            None => self.lock.store(false, Ordering::Release),
+        }
+    }
+}
+
+impl<T: Default> Default for PriorityMutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for PriorityMutex<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for PriorityMutex<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("PriorityMutex");
+
+        match self.try_lock() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+
+        d.finish_non_exhaustive()
+    }
+}
+
+/// An RAII implementation of a "scoped lock" of a [`PriorityMutex`]. When
+/// this structure is dropped, the lock is released or handed to the most
+/// urgent waiter.
+///
+/// This structure is created by the [`lock`], [`lock_with_priority`] and
+/// [`try_lock`] methods on [`PriorityMutex`].
+///
+/// [`lock`]: PriorityMutex::lock
+/// [`lock_with_priority`]: PriorityMutex::lock_with_priority
+/// [`try_lock`]: PriorityMutex::try_lock
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this mutex while it is held"
+)]
+pub struct PriorityMutexGuard<'m, T: ?Sized> {
+    mutex: &'m PriorityMutex<T>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for PriorityMutexGuard<'_, T> {}
+
+impl<T: ?Sized> Deref for PriorityMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for PriorityMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The lock is held, giving us exclusive access to the data.
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for PriorityMutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.mutex.unlock();
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for PriorityMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for PriorityMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
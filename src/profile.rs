@@ -0,0 +1,114 @@
+use crate::sync::atomic::{AtomicU8, Ordering};
+
+/// How the blocking operations of this crate wait for a lock or an event.
+///
+/// The profile is chosen for the whole program with [`configure`], so one
+/// binary can be tuned for its environment without recompiling: spinning
+/// suits dedicated cores, while yielding to the scheduler suits machines
+/// with more threads than cores. Without the `std` feature there is no
+/// scheduler to yield to, and every profile spins.
+///
+/// A [`Mutex`](crate::sync::Mutex) can instead fix how it waits with a
+/// [`RelaxStrategy`](crate::sync::RelaxStrategy), such as a
+/// [`SpinMutex`](crate::sync::SpinMutex).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[repr(u8)]
+pub enum Profile {
+    /// Spin with [`spin_loop`](core::hint::spin_loop) hints only.
+    Spin,
+    /// Spin briefly, then yield the thread on every further attempt.
+    #[default]
+    Adaptive,
+    /// Yield the thread after every failed attempt.
+    Yield,
+}
+
+impl Profile {
+    /// The number of failed attempts [`Adaptive`](Self::Adaptive) spins for
+    /// before it starts yielding.
+    pub const ADAPTIVE_SPINS: u32 = 10;
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Spin,
+            2 => Self::Yield,
+            _ => Self::Adaptive,
+        }
+    }
+}
+
+static PROFILE: AtomicU8 = AtomicU8::new(Profile::Adaptive as u8);
+
+/// Sets the waiting strategy used by every blocking operation of this crate.
+///
+/// This takes effect for waits that start after the call, and is usually
+/// done once at startup.
+///
+/// # Examples
+/// ```
+/// use skirt::Profile;
+///
+/// skirt::configure(Profile::Spin);
+/// assert_eq!(skirt::profile(), Profile::Spin);
+/// ```
+pub fn configure(profile: Profile) {
+    PROFILE.store(profile as u8, Ordering::Relaxed);
+}
+
+/// Returns the waiting strategy set with [`configure`].
+#[must_use]
+pub fn profile() -> Profile {
+    Profile::from_u8(PROFILE.load(Ordering::Relaxed))
+}
+
+/// The waiting state of a single blocking operation.
+pub struct Backoff {
+    spins: u32,
+}
+
+impl Backoff {
+    #[inline]
+    pub const fn new() -> Self {
+        Self { spins: 0 }
+    }
+
+    /// Waits a little after a failed attempt, according to the configured
+    /// [`Profile`].
+    #[inline]
+    pub fn relax(&mut self) {
+        self.relax_as(profile());
+    }
+
+    /// Waits a little after a failed attempt, according to `profile`
+    /// regardless of the configured one.
+    #[inline]
+    pub fn relax_as(&mut self, profile: Profile) {
+        core::hint::spin_loop();
+        self.spins = self.spins.saturating_add(1);
+
+        match profile {
+            Profile::Spin => {}
+            Profile::Adaptive if self.spins <= Profile::ADAPTIVE_SPINS => {}
+            // Without `std` there is no scheduler to yield to.
+            Profile::Adaptive | Profile::Yield => {
+                #[cfg(feature = "std")]
+                std::thread::yield_now();
+            }
+        }
+    }
+
+    /// Waits a little after a failed attempt, parking the thread for a
+    /// growing time, of up to about a millisecond, once spinning for
+    /// [`Profile::ADAPTIVE_SPINS`] attempts has not paid off.
+    #[inline]
+    pub fn park(&mut self) {
+        core::hint::spin_loop();
+        self.spins = self.spins.saturating_add(1);
+
+        #[cfg(feature = "std")]
+        if let Some(parks) = self.spins.checked_sub(Profile::ADAPTIVE_SPINS + 1) {
+            let timeout = std::time::Duration::from_micros(1 << parks.min(10));
+            std::thread::park_timeout(timeout);
+        }
+    }
+}
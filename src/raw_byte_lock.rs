@@ -0,0 +1,148 @@
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicU8, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A raw spinlock that fits in a single byte.
+///
+/// The lock protects no data of its own, which makes it suitable for
+/// embedding into space-constrained structures, such as one lock byte per
+/// slot of a slab, where a full [`Mutex`](crate::sync::Mutex) would not fit.
+/// Pairing it with the data it protects is up to the user.
+///
+/// The byte is in one of three states: unlocked, locked, or locked with
+/// other threads waiting, the latter of which can be queried with
+/// [`is_contended`](Self::is_contended).
+///
+/// # Examples
+/// ```
+/// use skirt::sync::RawByteLock;
+///
+/// let lock = RawByteLock::new();
+/// assert_eq!(size_of::<RawByteLock>(), 1);
+///
+/// lock.lock();
+/// assert!(!lock.try_lock());
+///
+/// // SAFETY: The lock is held by this thread.
+/// unsafe { lock.unlock() };
+/// assert!(lock.try_lock());
+/// ```
+#[repr(transparent)]
+pub struct RawByteLock {
+    state: AtomicU8,
+}
+
+impl RawByteLock {
+    const UNLOCKED: u8 = 0;
+    const LOCKED: u8 = 1;
+    const CONTENDED: u8 = 2;
+
+    /// An unlocked lock, usable where a constant is needed, such as in array
+    /// repeat expressions.
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const INIT: Self = Self::new();
+
+    /// Creates a new lock in an unlocked state.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(Self::UNLOCKED),
+        }
+    }
+
+    /// Acquires the lock, blocking the current thread until it is able to do
+    /// so.
+    pub fn lock(&self) {
+        if self.try_lock() {
+            return;
+        }
+
+        let mut backoff = Backoff::new();
+
+        while self.state.swap(Self::CONTENDED, Ordering::Acquire) != Self::UNLOCKED {
+            backoff.relax();
+        }
+    }
+
+    /// Attempts to acquire the lock without blocking, returning whether it
+    /// was acquired.
+    #[inline]
+    #[must_use]
+    pub fn try_lock(&self) -> bool {
+        self.state
+            .compare_exchange(
+                Self::UNLOCKED,
+                Self::LOCKED,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    /// Releases the lock.
+    ///
+    /// # Safety
+    /// The lock must be held by the current context, acquired through
+    /// [`lock`](Self::lock) or [`try_lock`](Self::try_lock).
+    #[inline]
+    pub unsafe fn unlock(&self) {
+        self.state.store(Self::UNLOCKED, Ordering::Release);
+    }
+
+    /// Returns whether the lock is currently held.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[inline]
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.state.load(Ordering::Relaxed) != Self::UNLOCKED
+    }
+
+    /// Returns whether the lock is currently held while other threads are
+    /// waiting for it.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[inline]
+    #[must_use]
+    pub fn is_contended(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == Self::CONTENDED
+    }
+}
+
+impl Default for RawByteLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for RawByteLock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RawByteLock")
+            .field("locked", &self.is_locked())
+            .finish()
+    }
+}
+
+#[cfg(feature = "lock_api")]
+unsafe impl lock_api::RawMutex for RawByteLock {
+    const INIT: Self = Self::new();
+
+    type GuardMarker = lock_api::GuardSend;
+
+    fn lock(&self) {
+        self.lock();
+    }
+
+    fn try_lock(&self) -> bool {
+        self.try_lock()
+    }
+
+    unsafe fn unlock(&self) {
+        unsafe { self.unlock() };
+    }
+
+    fn is_locked(&self) -> bool {
+        self.is_locked()
+    }
+}
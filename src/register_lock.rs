@@ -0,0 +1,141 @@
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A spinlock guarding a memory-mapped register.
+///
+/// Every access to the register is volatile, and [`modify`] holds the lock
+/// across its read and write, giving a correct read-modify-write when several
+/// threads share the same register. Plain [`read`]s and [`write`]s are
+/// serialized with it as well.
+///
+/// # Interrupt handlers
+/// The lock spins, so an interrupt handler that preempts a thread holding it
+/// on the same core spins forever. To share a register with an interrupt
+/// handler, mask that interrupt around every access from thread code, so the
+/// handler can never find the lock held by the code it interrupted.
+///
+/// [`modify`]: Self::modify
+/// [`read`]: Self::read
+/// [`write`]: Self::write
+///
+/// # Examples
+/// ```
+/// use skirt::sync::RegisterLock;
+///
+/// let mut control = 0b0001_u32;
+///
+/// // SAFETY: `control` outlives the lock and is only accessed through it.
+/// let register = unsafe { RegisterLock::new(&raw mut control) };
+///
+/// register.modify(|bits| *bits |= 0b0100);
+/// assert_eq!(register.read(), 0b0101);
+///
+/// register.write(0);
+/// assert_eq!(register.read(), 0);
+/// ```
+pub struct RegisterLock<T: Copy> {
+    lock: AtomicBool,
+    register: *mut T,
+}
+
+unsafe impl<T: Copy + Send> Send for RegisterLock<T> {}
+unsafe impl<T: Copy + Send> Sync for RegisterLock<T> {}
+
+impl<T: Copy> RegisterLock<T> {
+    /// Creates a new lock for the register at `register`.
+    ///
+    /// # Safety
+    /// `register` must be valid for volatile reads and writes of `T`, and
+    /// properly aligned, for as long as the lock is used. It should not be
+    /// accessed other than through this lock, or the read-modify-write
+    /// guarantee is lost.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn new(register: *mut T) -> Self {
+        Self {
+            lock: AtomicBool::new(false),
+            register,
+        }
+    }
+
+    /// Reads the register.
+    pub fn read(&self) -> T {
+        self.with_lock(|| {
+            // SAFETY: The register is valid for volatile reads, by the
+            // contract of `new`.
+            unsafe { self.register.read_volatile() }
+        })
+    }
+
+    /// Writes `value` to the register.
+    pub fn write(&self, value: T) {
+        self.with_lock(|| {
+            // SAFETY: The register is valid for volatile writes, by the
+            // contract of `new`.
+            unsafe { self.register.write_volatile(value) };
+        });
+    }
+
+    /// Reads the register, lets `f` modify the value, and writes it back,
+    /// all while holding the lock.
+    pub fn modify<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.with_lock(|| {
+            // SAFETY: The register is valid for volatile accesses, by the
+            // contract of `new`.
+            unsafe {
+                let mut value = self.register.read_volatile();
+                let result = f(&mut value);
+                self.register.write_volatile(value);
+                result
+            }
+        })
+    }
+
+    /// Returns the address of the register.
+    #[inline]
+    #[must_use]
+    pub const fn as_ptr(&self) -> *mut T {
+        self.register
+    }
+
+    fn with_lock<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let mut backoff = Backoff::new();
+
+        while self
+            .lock
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            backoff.relax();
+        }
+
+        let _unlock = Unlock(&self.lock);
+        f()
+    }
+}
+
+/// Releases the lock when dropped, so a panic in [`RegisterLock::modify`]
+/// does not leave it locked.
+struct Unlock<'a>(&'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<T: Copy> Debug for RegisterLock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RegisterLock")
+            .field("register", &self.register)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,91 @@
+use crate::profile::{Backoff, Profile};
+
+/// Decides how a [`Mutex`] waits between failed attempts to acquire it.
+///
+/// The strategy is a type parameter of the mutex, so a library can fix the
+/// waiting behaviour of its locks in its public API instead of inheriting
+/// the [`Profile`] the final binary [`configure`]s. It is one of
+/// [`Configured`], [`Spin`], [`Yield`] and [`Park`], and cannot be
+/// implemented outside of this crate.
+///
+/// Without the `std` feature there is no scheduler to hand the core to, and
+/// every strategy spins.
+///
+/// [`Mutex`]: crate::sync::Mutex
+/// [`configure`]: crate::configure
+pub trait RelaxStrategy: sealed::Strategy + Copy {}
+
+/// Wait according to the [`Profile`] set with [`configure`]. This is the
+/// default strategy of a [`Mutex`].
+///
+/// [`configure`]: crate::configure
+/// [`Mutex`]: crate::sync::Mutex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Configured;
+
+/// Spin with [`spin_loop`](core::hint::spin_loop) hints only, like
+/// [`Profile::Spin`]. This suits locks held for a few instructions on
+/// dedicated cores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Spin;
+
+/// Yield the thread after every failed attempt, like [`Profile::Yield`].
+/// This suits machines with more threads than cores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Yield;
+
+/// Spin briefly, then park the thread for a growing time of up to about a
+/// millisecond after every further attempt.
+///
+/// A parked thread gives its core up entirely, which suits locks that may
+/// be held for long. Nothing unparks it when the lock is released, so it
+/// may wake up to that long after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Park;
+
+impl RelaxStrategy for Configured {}
+impl RelaxStrategy for Spin {}
+impl RelaxStrategy for Yield {}
+impl RelaxStrategy for Park {}
+
+// Not `pub`, which would export it from `skirt::sync` along with the
+// strategies, and let other crates implement them.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) mod sealed {
+    use crate::profile::Backoff;
+
+    /// What a [`RelaxStrategy`](super::RelaxStrategy) does, kept out of the
+    /// public API.
+    pub trait Strategy: Sized {
+        /// Waits a little after a failed attempt.
+        fn relax(backoff: &mut Backoff);
+    }
+}
+
+impl sealed::Strategy for Configured {
+    #[inline]
+    fn relax(backoff: &mut Backoff) {
+        backoff.relax();
+    }
+}
+
+impl sealed::Strategy for Spin {
+    #[inline]
+    fn relax(backoff: &mut Backoff) {
+        backoff.relax_as(Profile::Spin);
+    }
+}
+
+impl sealed::Strategy for Yield {
+    #[inline]
+    fn relax(backoff: &mut Backoff) {
+        backoff.relax_as(Profile::Yield);
+    }
+}
+
+impl sealed::Strategy for Park {
+    #[inline]
+    fn relax(backoff: &mut Backoff) {
+        backoff.park();
+    }
+}
@@ -0,0 +1,143 @@
+use crate::mutex::Mutex;
+use crate::once_lock::OnceLock;
+use core::fmt::{Debug, Formatter};
+
+/// Decides whether a [`RetryLazy`] may attempt its initialization again.
+#[derive(Debug, Clone, Copy)]
+pub enum RetryPolicy {
+    /// Retry on every access until initialization succeeds.
+    Always,
+    /// Make at most this many attempts in total.
+    Attempts(u32),
+    /// Ask a function, given the number of failed attempts so far, whether
+    /// another attempt may be made now. This allows for backoff based on a
+    /// clock or other external state.
+    Backoff(fn(u32) -> bool),
+}
+
+impl RetryPolicy {
+    fn allows(self, failures: u32) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Attempts(max) => failures < max,
+            Self::Backoff(check) => check(failures),
+        }
+    }
+}
+
+/// The error returned when a [`RetryLazy`] has no value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryError<E> {
+    /// The initialization was attempted on this access and failed.
+    Failed(E),
+    /// The [`RetryPolicy`] did not allow another attempt.
+    Exhausted,
+}
+
+/// A value which is fallibly initialized on first access, retrying failed
+/// initializations according to a [`RetryPolicy`].
+///
+/// Unlike caching the first `Err` forever, a failed initialization is
+/// attempted again on a later access, which suits globals that depend on a
+/// network or on hardware that may not be ready yet. Attempts are serialized,
+/// and once an attempt succeeds the value never changes.
+///
+/// # Examples
+/// ```
+/// use core::sync::atomic::{AtomicU32, Ordering};
+/// use skirt::sync::{RetryError, RetryLazy, RetryPolicy};
+///
+/// static CALLS: AtomicU32 = AtomicU32::new(0);
+///
+/// static DEVICE: RetryLazy<u32, &str> = RetryLazy::new(
+///     || match CALLS.fetch_add(1, Ordering::Relaxed) {
+///         0 => Err("not ready"),
+///         _ => Ok(42),
+///     },
+///     RetryPolicy::Attempts(3),
+/// );
+///
+/// assert_eq!(RetryLazy::force(&DEVICE), Err(RetryError::Failed("not ready")));
+/// assert_eq!(RetryLazy::force(&DEVICE), Ok(&42));
+/// assert_eq!(RetryLazy::attempts(&DEVICE), 2);
+/// ```
+pub struct RetryLazy<T, E, F = fn() -> Result<T, E>> {
+    cell: OnceLock<T>,
+    attempts: Mutex<u32>,
+    policy: RetryPolicy,
+    init: F,
+    phantom: core::marker::PhantomData<fn() -> E>,
+}
+
+impl<T, E, F: Fn() -> Result<T, E>> RetryLazy<T, E, F> {
+    /// Creates a new value with the given initializing function and retry
+    /// policy.
+    #[inline]
+    pub const fn new(init: F, policy: RetryPolicy) -> Self {
+        Self {
+            cell: OnceLock::new(),
+            attempts: Mutex::new(0),
+            policy,
+            init,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Forces the evaluation of this value, returning a reference to it.
+    ///
+    /// If the value is not yet initialized and the policy allows it, the
+    /// initializing function is run. If another thread is currently running
+    /// it, this blocks until it is done.
+    ///
+    /// # Errors
+    /// Returns [`RetryError::Failed`] if the attempt made by this call failed,
+    /// and [`RetryError::Exhausted`] if the policy did not allow an attempt.
+    pub fn force(this: &Self) -> Result<&T, RetryError<E>> {
+        if let Some(value) = this.cell.get() {
+            return Ok(value);
+        }
+
+        let mut attempts = this.attempts.lock();
+
+        if let Some(value) = this.cell.get() {
+            return Ok(value);
+        }
+
+        if !this.policy.allows(*attempts) {
+            return Err(RetryError::Exhausted);
+        }
+
+        *attempts = attempts.saturating_add(1);
+
+        match (this.init)() {
+            Ok(value) => Ok(this.cell.get_or_init(|| value)),
+            Err(error) => Err(RetryError::Failed(error)),
+        }
+    }
+}
+
+impl<T, E, F> RetryLazy<T, E, F> {
+    /// Returns the value if it has been initialized.
+    #[inline]
+    pub fn get(this: &Self) -> Option<&T> {
+        this.cell.get()
+    }
+
+    /// Returns the number of initialization attempts made so far.
+    pub fn attempts(this: &Self) -> u32 {
+        *this.attempts.lock()
+    }
+}
+
+impl<T: Debug, E, F> Debug for RetryLazy<T, E, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_tuple("RetryLazy");
+
+        match Self::get(self) {
+            Some(v) => d.field(v),
+            None => d.field(&format_args!("<uninit>")),
+        };
+
+        d.finish()
+    }
+}
@@ -1,15 +1,34 @@
-use crate::sync::atomic::AtomicUsize;
+#[cfg(feature = "std")]
+use crate::deadline::Deadline;
+#[cfg(feature = "std")]
+use crate::error::TimeoutError;
+use crate::profile::Backoff;
+use crate::rwlock_policy::{RwLockPolicy, WriterPreferring};
+use crate::sync::atomic::{AtomicUsize, Ordering, fence};
+use core::alloc::Layout;
 use core::cell::UnsafeCell;
 use core::fmt::{Debug, Display, Formatter};
+use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
-const MASK: u8 = (1 << 6) - 1;
-const READ_LOCKED: u8 = 1;
-const WRITE_LOCKED: u8 = MASK;
-const READERS_WAITING: u8 = 1 << 5;
-const WRITERS_WAITING: u8 = 1 << 6;
-const MAX_READERS: u8 = MASK - 1;
-const DOWNGRADE: u8 = READ_LOCKED.wrapping_sub(WRITE_LOCKED);
+/// Set while a writer holds the lock.
+const WRITER: usize = 1;
+/// Set while a writer is waiting, which keeps new readers out so that a
+/// stream of readers cannot starve writers.
+const WRITER_WAITING: usize = 1 << 1;
+/// The amount each reader adds to the state.
+const READER: usize = 1 << 2;
+/// The amount each writer adds to the state when it releases the lock. The
+/// upper half of the state counts writes for optimistic readers.
+const WRITE: usize = 1 << (usize::BITS / 2);
+/// The bits of the state that count readers. Once they are all set, new
+/// readers wait for one to leave rather than overflowing the count.
+const READERS: usize = WRITE - READER;
+/// The bits of the state that count writes.
+const WRITES: usize = !(WRITE - 1);
 
 /// A reader-writer lock.
 ///
@@ -17,55 +36,1249 @@
 /// point in time. The write portion of this lock typically allows modification
 /// of the underlying data (exclusive access) and the read portion of this lock
 /// typically allows for read-only access (shared access).
-pub struct RwLock<T: ?Sized> {
+///
+/// Which side waits when readers and writers contend is chosen with the
+/// [`RwLockPolicy`] type parameter, given to [`with_policy`]. By default,
+/// writers are preferred: once a writer is waiting, new readers wait until
+/// it has acquired and released the lock.
+///
+/// Under heavy read concurrency, the state word shared by every reader
+/// bounces between cores. A [`BiasedRwLock`] avoids this at the expense of
+/// writers.
+///
+/// [`with_policy`]: Self::with_policy
+/// [`BiasedRwLock`]: crate::sync::BiasedRwLock
+///
+/// # Layout
+/// `RwLock<T, P>` is `#[repr(C)]`: the lock is a `usize` at offset `0`,
+/// followed by the state of the policy, which is empty for
+/// [`ReaderPreferring`] and [`WriterPreferring`] and two `usize`s for
+/// [`Fifo`], and then the protected data at [`DATA_OFFSET`]. The layout is
+/// the same under every feature set. It can be checked at compile time:
+///
+/// ```
+/// use skirt::sync::{Fifo, RwLock};
+///
+/// const WORD: usize = size_of::<usize>();
+///
+/// const _: () = assert!(<RwLock<u32>>::DATA_OFFSET == WORD);
+/// const _: () = assert!(<RwLock<u64, Fifo>>::DATA_OFFSET == 3 * WORD);
+/// ```
+///
+/// [`ReaderPreferring`]: crate::sync::ReaderPreferring
+/// [`Fifo`]: crate::sync::Fifo
+/// [`DATA_OFFSET`]: Self::DATA_OFFSET
+///
+/// # Examples
+/// ```
+/// use skirt::sync::RwLock;
+///
+/// let lock = RwLock::new(5);
+///
+/// {
+///     let r1 = lock.read();
+///     let r2 = lock.read();
+///     assert_eq!(*r1 + *r2, 10);
+/// }
+///
+/// *lock.write() += 1;
+/// assert_eq!(*lock.read(), 6);
+/// ```
+#[repr(C)]
+pub struct RwLock<T: ?Sized, P: RwLockPolicy = WriterPreferring> {
     lock: AtomicUsize,
+    queue: P::Queue,
     data: UnsafeCell<T>,
 }
 
-unsafe impl<T: ?Sized + Send> Send for RwLock<T> {}
-unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
+/// A version of an [`RwLock`] returned by [`RwLock::try_optimistic_read`],
+/// which tells whether a writer acquired the lock since.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stamp(usize);
+
+/// A snapshot of the state of an [`RwLock`], returned by [`RwLock::state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RwLockState(usize);
+
+impl RwLockState {
+    /// Returns whether any reader or writer held the lock.
+    #[inline]
+    #[must_use]
+    pub const fn is_locked(self) -> bool {
+        self.0 & (WRITER | READERS) != 0
+    }
+
+    /// Returns whether a writer held the lock.
+    #[inline]
+    #[must_use]
+    pub const fn is_locked_exclusive(self) -> bool {
+        self.0 & WRITER != 0
+    }
+
+    /// Returns whether a writer was waiting, which only the
+    /// [`WriterPreferring`] policy keeps track of.
+    #[inline]
+    #[must_use]
+    pub const fn writers_waiting(self) -> bool {
+        self.0 & WRITER_WAITING != 0
+    }
+
+    /// Returns the number of readers that held the lock.
+    #[inline]
+    #[must_use]
+    pub const fn readers(self) -> usize {
+        (self.0 & READERS) / READER
+    }
+}
+
+unsafe impl<T: ?Sized + Send, P: RwLockPolicy> Send for RwLock<T, P> {}
+unsafe impl<T: ?Sized + Send + Sync, P: RwLockPolicy> Sync for RwLock<T, P> {}
 
 impl<T> RwLock<T> {
+    /// Creates a new lock in an unlocked state ready for use.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// ```
     #[inline]
     pub const fn new(data: T) -> Self {
+        Self::with_policy(data, WriterPreferring)
+    }
+}
+
+impl<T, P: RwLockPolicy> RwLock<T, P> {
+    /// The offset in bytes of the protected data from the start of the lock.
+    pub const DATA_OFFSET: usize = core::mem::offset_of!(Self, data);
+
+    /// Returns the memory layout of an `RwLock<T, P>`.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let layout = <RwLock<u8>>::layout();
+    /// assert_eq!(layout.size(), 2 * size_of::<usize>());
+    /// assert_eq!(layout.align(), align_of::<usize>());
+    /// ```
+    #[must_use]
+    pub const fn layout() -> Layout {
+        Layout::new::<Self>()
+    }
+
+    /// Creates a new lock in an unlocked state, which admits contending
+    /// readers and writers according to the policy `P`.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Fifo, RwLock};
+    ///
+    /// let lock = RwLock::with_policy(0, Fifo);
+    ///
+    /// *lock.write() += 1;
+    /// assert_eq!(*lock.read(), 1);
+    /// ```
+    #[inline]
+    pub const fn with_policy(data: T, _policy: P) -> Self {
         Self {
             lock: AtomicUsize::new(0),
+            queue: P::QUEUE,
             data: UnsafeCell::new(data),
         }
     }
+
+    /// Consumes this lock, returning the underlying data.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// assert_eq!(lock.into_inner(), 0);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Returns the contained value by cloning it under a read lock.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(7);
+    /// assert_eq!(lock.get_cloned(), 7);
+    /// ```
+    pub fn get_cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        (*self.read()).clone()
+    }
+
+    /// Sets the contained value to `data`, dropping the old value after the
+    /// lock is released.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(7);
+    ///
+    /// lock.set(11);
+    /// assert_eq!(lock.get_cloned(), 11);
+    /// ```
+    pub fn set(&self, data: T) {
+        drop(self.replace(data));
+    }
+
+    /// Replaces the contained value with `data`, and returns the old contained value.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(7);
+    ///
+    /// assert_eq!(lock.replace(11), 7);
+    /// assert_eq!(lock.get_cloned(), 11);
+    /// ```
+    pub fn replace(&self, data: T) -> T {
+        core::mem::replace(&mut *self.write(), data)
+    }
 }
 
-impl<T: ?Sized> RwLock<T> {
-    pub fn read(&self) {}
+impl<T: ?Sized, P: RwLockPolicy> RwLock<T, P> {
+    /// Keeps new readers out while a writer holds the lock, and under the
+    /// writer-preferring policy, while a writer is waiting.
+    const BLOCKING: usize = if P::PREFER_WRITERS {
+        WRITER | WRITER_WAITING
+    } else {
+        WRITER
+    };
+
+    /// Locks this lock with shared read access, blocking the current thread
+    /// until it can be acquired.
+    ///
+    /// There may be other readers inside the lock when this returns, but no
+    /// writer. If the maximum number of readers is inside, which takes
+    /// forgetting guards on purpose in practice, this waits for one to leave.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let lock = Arc::new(RwLock::new(1));
+    /// let c_lock = Arc::clone(&lock);
+    ///
+    /// let r = lock.read();
+    /// assert_eq!(*r, 1);
+    ///
+    /// thread::spawn(move || {
+    ///     let r = c_lock.read();
+    ///     assert_eq!(*r, 1);
+    /// }).join().unwrap();
+    /// ```
+    pub fn read(&self) -> RwLockReadGuard<'_, T, P> {
+        P::in_turn(&self.queue, || {
+            spin(|| self.try_read_unless(Self::BLOCKING))
+        })
+    }
+
+    /// Attempts to acquire this lock with shared read access.
+    ///
+    /// This fails if a writer holds the lock, if another acquisition has
+    /// priority over this one according to the [`RwLockPolicy`], or if the
+    /// maximum number of readers is inside. This function does not block.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// assert_eq!(*lock.try_read().unwrap(), 1);
+    ///
+    /// let w = lock.write();
+    /// assert!(lock.try_read().is_none());
+    /// # drop(w);
+    /// ```
+    #[must_use]
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T, P>> {
+        P::try_in_turn(&self.queue, || self.try_read_unless(Self::BLOCKING))
+    }
 
-    pub fn try_read(&self) {}
+    /// Attempts to acquire this lock with shared read access, like
+    /// [`try_read`], but may fail spuriously even when it could succeed.
+    ///
+    /// This makes a single weak compare-and-swap of the state, where
+    /// [`try_read`] retries as long as only other readers interfere. It is
+    /// meant for callers that retry in a loop of their own anyway, such as
+    /// one that checks the [`state`] in between, where it compiles to
+    /// cheaper code on platforms with load-linked/store-conditional.
+    ///
+    /// [`try_read`]: Self::try_read
+    /// [`state`]: Self::state
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let guard = loop {
+    ///     if lock.state().is_locked_exclusive() {
+    ///         // Do something else while a writer is busy.
+    ///         continue;
+    ///     }
+    ///
+    ///     if let Some(guard) = lock.try_read_weak() {
+    ///         break guard;
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(*guard, 1);
+    /// ```
+    #[must_use]
+    pub fn try_read_weak(&self) -> Option<RwLockReadGuard<'_, T, P>> {
+        P::try_in_turn(&self.queue, || {
+            let state = self.lock.load(Ordering::Relaxed);
 
-    pub fn write(&self) {}
+            (state & Self::BLOCKING == 0
+                && state & READERS != READERS
+                && self
+                    .lock
+                    .compare_exchange_weak(
+                        state,
+                        state + READER,
+                        Ordering::Acquire,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok())
+            .then(|| RwLockReadGuard::new(self))
+        })
+    }
+
+    /// Locks this lock with shared read access, ignoring waiting writers,
+    /// and blocking the current thread only while a writer holds the lock.
+    ///
+    /// A thread that already holds a read lock and calls [`read`] again
+    /// deadlocks if a writer started waiting in between, since the writer
+    /// waits for the first read lock to be released and the second read
+    /// waits for the writer. This method does not wait for queued writers,
+    /// so it is safe to use in call chains that take the read lock at
+    /// several levels. Using it for every read can starve writers.
+    ///
+    /// [`read`]: Self::read
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let outer = lock.read();
+    /// // Even if a writer is queued now, this does not deadlock.
+    /// let inner = lock.read_recursive();
+    /// assert_eq!(*outer + *inner, 2);
+    /// ```
+    pub fn read_recursive(&self) -> RwLockReadGuard<'_, T, P> {
+        spin(|| self.try_read_recursive())
+    }
+
+    /// Attempts to acquire this lock with shared read access, ignoring
+    /// waiting writers.
+    ///
+    /// This only fails if a writer holds the lock, or if the maximum number
+    /// of readers is inside. See [`read_recursive`].
+    ///
+    /// [`read_recursive`]: Self::read_recursive
+    #[must_use]
+    pub fn try_read_recursive(&self) -> Option<RwLockReadGuard<'_, T, P>> {
+        self.try_read_unless(WRITER)
+    }
+
+    /// Locks this lock with exclusive write access, blocking the current
+    /// thread until it can be acquired.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let mut w = lock.write();
+    /// *w = 2;
+    /// assert!(lock.try_read().is_none());
+    /// ```
+    pub fn write(&self) -> RwLockWriteGuard<'_, T, P> {
+        P::in_turn(&self.queue, || {
+            spin(|| {
+                let guard = self.try_acquire_write(false);
+
+                if guard.is_none() {
+                    self.announce_writer();
+                }
+
+                guard
+            })
+        })
+    }
+
+    /// Attempts to acquire this lock with exclusive write access.
+    ///
+    /// This fails if the lock is held by a writer or any readers, or if
+    /// others are queued under the [`Fifo`] policy. This function does not
+    /// block.
+    ///
+    /// [`Fifo`]: crate::sync::Fifo
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let r = lock.read();
+    /// assert!(lock.try_write().is_none());
+    ///
+    /// drop(r);
+    /// *lock.try_write().unwrap() = 2;
+    /// assert_eq!(*lock.read(), 2);
+    /// ```
+    #[must_use]
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T, P>> {
+        P::try_in_turn(&self.queue, || self.try_acquire_write(false))
+    }
+
+    /// Attempts to acquire this lock with exclusive write access, like
+    /// [`try_write`], but may fail spuriously even when it could succeed.
+    ///
+    /// This uses a weak compare-and-swap of the state, for callers that retry
+    /// in a loop of their own anyway. See [`try_read_weak`].
+    ///
+    /// [`try_write`]: Self::try_write
+    /// [`try_read_weak`]: Self::try_read_weak
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let mut guard = loop {
+    ///     if let Some(guard) = lock.try_write_weak() {
+    ///         break guard;
+    ///     }
+    /// };
+    ///
+    /// *guard += 1;
+    /// assert!(lock.try_read().is_none());
+    /// ```
+    #[must_use]
+    pub fn try_write_weak(&self) -> Option<RwLockWriteGuard<'_, T, P>> {
+        P::try_in_turn(&self.queue, || self.try_acquire_write(true))
+    }
+
+    /// Attempts to acquire this lock with shared read access, giving up
+    /// after `timeout`.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    /// use std::time::Duration;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let w = lock.write();
+    /// assert!(lock.try_read_for(Duration::from_millis(10)).is_none());
+    ///
+    /// drop(w);
+    /// assert_eq!(*lock.try_read_for(Duration::from_millis(10)).unwrap(), 1);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn try_read_for(&self, timeout: Duration) -> Option<RwLockReadGuard<'_, T, P>> {
+        self.try_read_until(Deadline::after(timeout).instant())
+    }
+
+    /// Attempts to acquire this lock with shared read access, giving up once
+    /// `deadline` has passed.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn try_read_until(&self, deadline: Instant) -> Option<RwLockReadGuard<'_, T, P>> {
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Some(guard) = self.try_read() {
+                return Some(guard);
+            }
+
+            if Instant::now() >= deadline {
+                return None;
+            }
+
+            backoff.relax();
+        }
+    }
+
+    /// Attempts to acquire this lock with exclusive write access, giving up
+    /// after `timeout`.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    /// use std::time::Duration;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let r = lock.read();
+    /// assert!(lock.try_write_for(Duration::from_millis(10)).is_none());
+    ///
+    /// // The reader was not locked out by the writer that gave up.
+    /// assert!(lock.try_read().is_some());
+    /// # drop(r);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn try_write_for(&self, timeout: Duration) -> Option<RwLockWriteGuard<'_, T, P>> {
+        self.try_write_until(Deadline::after(timeout).instant())
+    }
+
+    /// Attempts to acquire this lock with exclusive write access, giving up
+    /// once `deadline` has passed.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn try_write_until(&self, deadline: Instant) -> Option<RwLockWriteGuard<'_, T, P>> {
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Some(guard) = self.try_write() {
+                return Some(guard);
+            }
+
+            if Instant::now() >= deadline {
+                // Let readers in again. Any other waiting writer sets the
+                // flag again on its next attempt.
+                if P::PREFER_WRITERS {
+                    self.lock.fetch_and(!WRITER_WAITING, Ordering::Relaxed);
+                }
+
+                return None;
+            }
+
+            self.announce_writer();
+
+            backoff.relax();
+        }
+    }
+
+    /// Acquires this lock with shared read access, giving up once `deadline`
+    /// has passed.
+    ///
+    /// # Errors
+    /// Returns [`TimeoutError`] if the lock could not be acquired before the
+    /// deadline.
+    #[cfg(feature = "std")]
+    pub fn read_until(
+        &self,
+        deadline: &Deadline,
+    ) -> Result<RwLockReadGuard<'_, T, P>, TimeoutError> {
+        self.try_read_until(deadline.instant()).ok_or(TimeoutError)
+    }
+
+    /// Acquires this lock with exclusive write access, giving up once
+    /// `deadline` has passed.
+    ///
+    /// # Errors
+    /// Returns [`TimeoutError`] if the lock could not be acquired before the
+    /// deadline.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{Deadline, RwLock};
+    /// use std::time::Duration;
+    ///
+    /// let lock = RwLock::new(0);
+    /// let deadline = Deadline::after(Duration::from_millis(10));
+    ///
+    /// let reader = lock.read_until(&deadline).unwrap();
+    /// assert!(lock.write_until(&deadline).is_err());
+    /// # drop(reader);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_until(
+        &self,
+        deadline: &Deadline,
+    ) -> Result<RwLockWriteGuard<'_, T, P>, TimeoutError> {
+        self.try_write_until(deadline.instant()).ok_or(TimeoutError)
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `RwLock` mutably, no actual locking needs
+    /// to take place -- the mutable borrow statically guarantees no locks
+    /// exist.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let mut lock = RwLock::new(0);
+    /// *lock.get_mut() = 10;
+    /// assert_eq!(*lock.read(), 10);
+    /// ```
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    /// Returns a raw pointer to the underlying data.
+    ///
+    /// This is meant for passing the data to code that is synchronized
+    /// externally, such as C APIs. The pointer is valid for as long as the
+    /// lock is, but reading or writing through it is only sound while that
+    /// access is otherwise guaranteed not to race, for example while holding
+    /// a matching guard.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new([0u8; 4]);
+    ///
+    /// let guard = lock.write();
+    /// // SAFETY: The write lock is held, so nothing else accesses the buffer.
+    /// unsafe { lock.data_ptr().cast::<u8>().write(7) };
+    /// drop(guard);
+    ///
+    /// assert_eq!(lock.read()[0], 7);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn data_ptr(&self) -> *mut T {
+        self.data.get()
+    }
+
+    /// Calls `f` with shared access to the data, holding a read lock for the
+    /// duration of the call.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(vec![1, 2, 3]);
+    /// assert_eq!(lock.with_read(|v| v.len()), 3);
+    /// ```
+    pub fn with_read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.read())
+    }
+
+    /// Calls `f` with exclusive access to the data, holding the write lock
+    /// for the duration of the call.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(vec![1, 2, 3]);
+    ///
+    /// lock.with_write(|v| v.push(4));
+    /// assert_eq!(lock.with_read(|v| v.len()), 4);
+    /// ```
+    pub fn with_write<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.write())
+    }
+
+    /// Releases a read lock without a guard.
+    ///
+    /// This is meant for FFI code and interrupt handlers that were handed
+    /// raw read access, whose guard was given up with [`mem::forget`].
+    ///
+    /// [`mem::forget`]: core::mem::forget
+    ///
+    /// # Safety
+    /// The lock must be held for reading, with the guard of that read
+    /// access forgotten.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// core::mem::forget(lock.read());
+    ///
+    /// // SAFETY: The read guard above was forgotten.
+    /// unsafe { lock.force_unlock_read() };
+    /// assert!(!lock.is_locked());
+    /// ```
+    pub unsafe fn force_unlock_read(&self) {
+        unlock_read(&self.lock);
+    }
+
+    /// Releases the write lock without a guard.
+    ///
+    /// This is meant for FFI code and interrupt handlers that were handed
+    /// raw write access, whose guard was given up with [`mem::forget`].
+    ///
+    /// [`mem::forget`]: core::mem::forget
+    ///
+    /// # Safety
+    /// The lock must be held for writing, with the guard of that write
+    /// access forgotten.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// core::mem::forget(lock.write());
+    ///
+    /// // SAFETY: The write guard above was forgotten.
+    /// unsafe { lock.force_unlock_write() };
+    /// assert!(!lock.is_locked());
+    /// ```
+    pub unsafe fn force_unlock_write(&self) {
+        unlock_write(&self.lock);
+    }
+
+    /// Forcibly unlocks the lock, regardless of who holds it.
+    ///
+    /// This is an escape hatch for recovering a lock whose guards were
+    /// leaked or whose owners were killed while holding it. All readers and
+    /// the writer are released at once, and under the [`Fifo`] policy the
+    /// queue of waiting threads is emptied. The protected data is left as
+    /// the last writer wrote it, which may be halfway through an update.
+    ///
+    /// This also forgets the writes counted so far, so a [`Stamp`] taken
+    /// before the reset may validate afterwards.
+    ///
+    /// [`Fifo`]: crate::sync::Fifo
+    ///
+    /// # Safety
+    /// No guard of this lock may be used after this call, and no thread may
+    /// be acquiring it or holding a [`Stamp`] of it concurrently. Resetting a
+    /// lock whose guard is still alive allows a writer to access the data
+    /// alongside other threads.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// core::mem::forget(lock.read());
+    /// core::mem::forget(lock.read());
+    /// assert!(lock.try_write().is_none());
+    ///
+    /// // SAFETY: Both read guards were leaked and can never be used again.
+    /// unsafe { lock.force_reset() };
+    /// assert!(lock.try_write().is_some());
+    /// ```
+    #[inline]
+    pub unsafe fn force_reset(&self) {
+        P::reset(&self.queue);
+        self.lock.store(0, Ordering::Release);
+    }
 
-    pub fn try_write(&self) {}
+    /// Starts an optimistic read, returning a [`Stamp`] to [`validate`] it
+    /// with, or [`None`] if a writer holds the lock.
+    ///
+    /// An optimistic read does not write to the lock at all, so readers of
+    /// small data do not contend with each other. The data read in between
+    /// may be torn by a concurrent writer, and must only be trusted once the
+    /// stamp is validated. [`read_optimistic`] does all of this for [`Copy`]
+    /// data.
+    ///
+    /// Writes are counted in the upper half of the lock's state word, so a
+    /// stamp also validates after a multiple of 2<sup>16</sup> writes on
+    /// 32-bit targets, or 2<sup>32</sup> writes on 64-bit targets.
+    ///
+    /// [`validate`]: Self::validate
+    /// [`read_optimistic`]: Self::read_optimistic
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    ///
+    /// let stamp = lock.try_optimistic_read().unwrap();
+    /// assert!(lock.validate(stamp));
+    ///
+    /// *lock.write() += 1;
+    /// assert!(!lock.validate(stamp));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn try_optimistic_read(&self) -> Option<Stamp> {
+        let state = self.lock.load(Ordering::Acquire);
+        (state & WRITER == 0).then_some(Stamp(state & WRITES))
+    }
+
+    /// Returns whether no writer has acquired the lock since `stamp` was
+    /// returned by [`try_optimistic_read`](Self::try_optimistic_read).
+    #[inline]
+    #[must_use]
+    pub fn validate(&self, stamp: Stamp) -> bool {
+        fence(Ordering::Acquire);
+        self.lock.load(Ordering::Relaxed) & (WRITES | WRITER) == stamp.0
+    }
+
+    /// Returns a copy of the data, read optimistically without writing to
+    /// the lock, and falling back to [`read`](Self::read) if a writer gets
+    /// in the way.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let position = RwLock::new((3, 4));
+    /// assert_eq!(position.read_optimistic(), (3, 4));
+    /// ```
+    pub fn read_optimistic(&self) -> T
+    where
+        T: Copy,
+    {
+        if let Some(stamp) = self.try_optimistic_read() {
+            // SAFETY: A writer may modify the data concurrently, so it is
+            // read as possibly torn bytes, and only assumed to be a valid
+            // `T` once the stamp shows that no writer touched it meanwhile.
+            let data = unsafe { self.data.get().cast::<MaybeUninit<T>>().read_volatile() };
+
+            if self.validate(stamp) {
+                // SAFETY: No writer touched the data while it was read.
+                return unsafe { data.assume_init() };
+            }
+        }
+
+        *self.read()
+    }
+
+    /// Returns whether the lock is held by a writer or any readers.
+    ///
+    /// This is a single relaxed load, meant for debug assertions and
+    /// metrics. It is only a hint and may be outdated by the time it is used.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// assert!(!lock.is_locked());
+    ///
+    /// let guard = lock.read();
+    /// assert!(lock.is_locked());
+    /// # drop(guard);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.lock.load(Ordering::Relaxed) & (WRITER | READERS) != 0
+    }
+
+    /// Returns whether the lock is held by a writer.
+    ///
+    /// Like [`is_locked`](Self::is_locked), this is only a hint.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    ///
+    /// let guard = lock.read();
+    /// assert!(!lock.is_locked_exclusive());
+    /// drop(guard);
+    ///
+    /// let guard = lock.write();
+    /// assert!(lock.is_locked_exclusive());
+    /// # drop(guard);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_locked_exclusive(&self) -> bool {
+        self.lock.load(Ordering::Relaxed) & WRITER != 0
+    }
+
+    /// Returns whether a writer is waiting for the lock, meaning a call to
+    /// [`read`] would have to wait even if no writer holds it.
+    ///
+    /// This is a single relaxed load, like [`is_locked`]. Only the
+    /// [`WriterPreferring`] policy keeps track of waiting writers, so this is
+    /// always false under the other policies.
+    ///
+    /// [`read`]: Self::read
+    /// [`is_locked`]: Self::is_locked
+    /// [`WriterPreferring`]: crate::sync::WriterPreferring
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    /// use std::thread;
+    ///
+    /// let lock = RwLock::new(0);
+    /// assert!(!lock.writers_waiting());
+    ///
+    /// thread::scope(|scope| {
+    ///     let reader = lock.read();
+    ///     scope.spawn(|| *lock.write() += 1);
+    ///
+    ///     while !lock.writers_waiting() {
+    ///         thread::yield_now();
+    ///     }
+    ///
+    ///     drop(reader);
+    /// });
+    ///
+    /// assert!(!lock.writers_waiting());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn writers_waiting(&self) -> bool {
+        self.lock.load(Ordering::Relaxed) & WRITER_WAITING != 0
+    }
+
+    /// Returns the number of readers holding the lock.
+    ///
+    /// Like [`is_locked`](Self::is_locked), this is only a hint.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    ///
+    /// let a = lock.read();
+    /// let b = lock.read();
+    /// assert_eq!(lock.reader_count(), 2);
+    /// # drop((a, b));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn reader_count(&self) -> usize {
+        (self.lock.load(Ordering::Relaxed) & READERS) / READER
+    }
+
+    /// Returns a snapshot of the state of the lock, taken with a single
+    /// relaxed load.
+    ///
+    /// Unlike calling [`reader_count`], [`is_locked_exclusive`] and
+    /// [`writers_waiting`] one after another, the parts of the snapshot are
+    /// consistent with each other. Like them, it may be outdated by the time
+    /// it is used, so it is only good for deciding what to try next, as in a
+    /// loop around [`try_read_weak`] or [`try_write_weak`].
+    ///
+    /// [`reader_count`]: Self::reader_count
+    /// [`is_locked_exclusive`]: Self::is_locked_exclusive
+    /// [`writers_waiting`]: Self::writers_waiting
+    /// [`try_read_weak`]: Self::try_read_weak
+    /// [`try_write_weak`]: Self::try_write_weak
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(0);
+    /// let reader = lock.read();
+    ///
+    /// let state = lock.state();
+    /// assert_eq!(state.readers(), 1);
+    /// assert!(!state.is_locked_exclusive());
+    /// # drop(reader);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn state(&self) -> RwLockState {
+        RwLockState(self.lock.load(Ordering::Relaxed))
+    }
+
+    /// Takes the write lock if it is free, regardless of the policy.
+    fn try_acquire_write(&self, weak: bool) -> Option<RwLockWriteGuard<'_, T, P>> {
+        let state = self.lock.load(Ordering::Relaxed);
+
+        if state & (WRITER | READERS) != 0 {
+            return None;
+        }
+
+        let locked = state & WRITES | WRITER;
+        let result = if weak {
+            self.lock
+                .compare_exchange_weak(state, locked, Ordering::Acquire, Ordering::Relaxed)
+        } else {
+            self.lock
+                .compare_exchange(state, locked, Ordering::Acquire, Ordering::Relaxed)
+        };
+
+        if result.is_err() {
+            return None;
+        }
+
+        // Order the flag before any write to the data, so optimistic readers
+        // that see the write also see the flag.
+        fence(Ordering::Release);
+
+        Some(RwLockWriteGuard::new(self))
+    }
+
+    /// Turns the read lock of a downgraded writer back into the write lock,
+    /// once the readers that got in meanwhile have left.
+    ///
+    /// No other writer can get in first, since the read lock keeps them out.
+    fn upgrade(&self) {
+        let mut backoff = Backoff::new();
+
+        loop {
+            let state = self.lock.load(Ordering::Relaxed);
+
+            if state & READERS == READER
+                && self
+                    .lock
+                    .compare_exchange_weak(
+                        state,
+                        state & WRITES | WRITER,
+                        Ordering::Acquire,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+            {
+                // As in `try_acquire_write`.
+                fence(Ordering::Release);
+                return;
+            }
+
+            self.announce_writer();
+            backoff.relax();
+        }
+    }
+
+    /// Adds a reader, unless any of the `blocking` bits are set or the
+    /// reader count is saturated.
+    fn try_read_unless(&self, blocking: usize) -> Option<RwLockReadGuard<'_, T, P>> {
+        let mut state = self.lock.load(Ordering::Relaxed);
+
+        loop {
+            if state & blocking != 0 || state & READERS == READERS {
+                return None;
+            }
+
+            match self.lock.compare_exchange_weak(
+                state,
+                state + READER,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(RwLockReadGuard::new(self)),
+                Err(current) => state = current,
+            }
+        }
+    }
+
+    /// Announces a waiting writer so that no new readers get in. Acquiring
+    /// the lock clears the flag, and any other waiting writer sets it again
+    /// on its next attempt.
+    pub(crate) fn announce_writer(&self) {
+        if P::PREFER_WRITERS && self.lock.load(Ordering::Relaxed) & WRITER_WAITING == 0 {
+            self.lock.fetch_or(WRITER_WAITING, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Retries `acquire` with the configured backoff until it succeeds.
+fn spin<G>(mut acquire: impl FnMut() -> Option<G>) -> G {
+    let mut backoff = Backoff::new();
+
+    loop {
+        if let Some(guard) = acquire() {
+            return guard;
+        }
+
+        backoff.relax();
+    }
+}
+
+/// Releases a read lock on the state word of an [`RwLock`], which mapped
+/// guards hold without knowing the type of the data.
+fn unlock_read(lock: &AtomicUsize) {
+    lock.fetch_sub(READER, Ordering::Release);
+}
+
+/// Releases the write lock on the state word of an [`RwLock`], counting the
+/// write for optimistic readers.
+fn unlock_write(lock: &AtomicUsize) {
+    // Keep the flag of any writer that started waiting meanwhile.
+    lock.fetch_add(WRITE - WRITER, Ordering::Release);
+}
+
+/// Turns the write lock on the state word of an [`RwLock`] into a read lock
+/// in one step, counting the write for optimistic readers.
+fn downgrade_write(lock: &AtomicUsize) {
+    lock.fetch_add(WRITE - WRITER + READER, Ordering::Release);
+}
+
+/// Upgrades the read lock of a downgraded writer back to the write lock when
+/// dropped, even if the closure that read in between panicked.
+struct Upgrade<'rw, T: ?Sized, P: RwLockPolicy>(&'rw RwLock<T, P>);
+
+impl<T: ?Sized, P: RwLockPolicy> Drop for Upgrade<'_, T, P> {
+    fn drop(&mut self) {
+        self.0.upgrade();
+    }
 }
 
 impl<T> From<T> for RwLock<T> {
+    /// Creates a new lock in an unlocked state ready for use.
+    /// This is equivalent to [`RwLock::new`].
     fn from(data: T) -> Self {
         Self::new(data)
     }
 }
 
 impl<T: Default> Default for RwLock<T> {
+    /// Creates a `RwLock<T>`, with the `Default` value for T.
     fn default() -> Self {
         Self::new(T::default())
     }
 }
 
-impl<T: ?Sized + Debug> Debug for RwLock<T> {
+impl<T: ?Sized + Debug, P: RwLockPolicy> Debug for RwLock<T, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("RwLock");
+        d.field("readers", &self.reader_count());
+        d.field("writer", &self.is_locked_exclusive());
+        d.field("writer_waiting", &self.writers_waiting());
+
+        match self.try_read() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+
+        d.finish_non_exhaustive()
+    }
+}
+
+/// RAII structure used to release the shared read access of a lock when
+/// dropped.
+///
+/// This structure is created by the [`read`] and [`try_read`] methods
+/// on [`RwLock`].
+///
+/// [`read`]: RwLock::read
+/// [`try_read`]: RwLock::try_read
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "writers spin on this lock while it is held"
+)]
+pub struct RwLockReadGuard<'rw, T: ?Sized + 'rw, P: RwLockPolicy = WriterPreferring> {
+    lock: &'rw RwLock<T, P>,
+    #[cfg(not(feature = "nightly"))]
+    phantom: core::marker::PhantomData<*const ()>,
+}
+
+#[cfg(feature = "nightly")]
+impl<T: ?Sized, P: RwLockPolicy> !Send for RwLockReadGuard<'_, T, P> {}
+unsafe impl<T: ?Sized + Sync, P: RwLockPolicy> Sync for RwLockReadGuard<'_, T, P> {}
+
+impl<'rw, T: ?Sized, P: RwLockPolicy> RwLockReadGuard<'rw, T, P> {
+    const fn new(lock: &'rw RwLock<T, P>) -> Self {
+        Self {
+            lock,
+            #[cfg(not(feature = "nightly"))]
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns a raw pointer to the locked data, valid for reads while the
+    /// guard is held.
+    ///
+    /// This is an associated function, so that it does not shadow a method
+    /// of the data.
+    #[inline]
+    #[must_use]
+    pub const fn data_ptr(this: &Self) -> *const T {
+        this.lock.data.get()
+    }
+
+    /// Makes a [`MappedRwLockReadGuard`] for a component of the locked data.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{RwLock, RwLockReadGuard};
+    ///
+    /// let lock = RwLock::new((0, 'a'));
+    ///
+    /// let guard = RwLockReadGuard::map(lock.read(), |data| &data.1);
+    /// assert_eq!(*guard, 'a');
+    /// ```
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedRwLockReadGuard<'rw, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let lock = this.lock;
+        core::mem::forget(this);
+
+        // SAFETY: A read lock is held, so there is no writer.
+        let data = NonNull::from(f(unsafe { &*lock.data.get() }));
+        MappedRwLockReadGuard::new(&lock.lock, data)
+    }
+
+    /// Attempts to make a [`MappedRwLockReadGuard`] for a component of the
+    /// locked data, handing back the original guard if `f` returns [`None`].
+    ///
+    /// # Errors
+    /// Returns the original guard if `f` returns [`None`].
+    pub fn try_map<U: ?Sized, F>(this: Self, f: F) -> Result<MappedRwLockReadGuard<'rw, U>, Self>
+    where
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        // SAFETY: A read lock is held, so there is no writer.
+        match f(unsafe { &*this.lock.data.get() }) {
+            Some(data) => {
+                let data = NonNull::from(data);
+                let lock = this.lock;
+                core::mem::forget(this);
+                Ok(MappedRwLockReadGuard::new(&lock.lock, data))
+            }
+            None => Err(this),
+        }
+    }
+}
+
+impl<T: ?Sized, P: RwLockPolicy> Deref for RwLockReadGuard<'_, T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: A read lock is held, so there is no writer.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized, P: RwLockPolicy> Drop for RwLockReadGuard<'_, T, P> {
+    #[inline]
+    fn drop(&mut self) {
+        unlock_read(&self.lock.lock);
+    }
+}
+
+impl<T: ?Sized + Debug, P: RwLockPolicy> Debug for RwLockReadGuard<'_, T, P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        todo!()
+        (**self).fmt(f)
     }
 }
 
-// pub struct RwLockReadGuard<'r, T: ?Sized + 'r> {
-//     data: NonNull<T>,
-// }
+impl<T: ?Sized + Display, P: RwLockPolicy> Display for RwLockReadGuard<'_, T, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
 
 /// RAII structure used to release the exclusive write access of a lock when
 /// dropped.
@@ -75,54 +1288,598 @@ fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
 ///
 /// [`write`]: RwLock::write
 /// [`try_write`]: RwLock::try_write
-pub struct RwLockWriteGuard<'rw, T: ?Sized + 'rw> {
-    lock: &'rw RwLock<T>,
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this lock while it is held"
+)]
+pub struct RwLockWriteGuard<'rw, T: ?Sized + 'rw, P: RwLockPolicy = WriterPreferring> {
+    lock: &'rw RwLock<T, P>,
     #[cfg(not(feature = "nightly"))]
     phantom: core::marker::PhantomData<*const ()>,
 }
 
 #[cfg(feature = "nightly")]
-impl<T: ?Sized> !Send for RwLockWriteGuard<'_, T> {}
-unsafe impl<T: ?Sized + Sync> Sync for RwLockWriteGuard<'_, T> {}
+impl<T: ?Sized, P: RwLockPolicy> !Send for RwLockWriteGuard<'_, T, P> {}
+unsafe impl<T: ?Sized + Sync, P: RwLockPolicy> Sync for RwLockWriteGuard<'_, T, P> {}
 
-impl<'rw, T: ?Sized> RwLockWriteGuard<'rw, T> {
-    const fn new(lock: &'rw RwLock<T>) -> Self {
+impl<'rw, T: ?Sized, P: RwLockPolicy> RwLockWriteGuard<'rw, T, P> {
+    const fn new(lock: &'rw RwLock<T, P>) -> Self {
         Self {
             lock,
             #[cfg(not(feature = "nightly"))]
             phantom: core::marker::PhantomData,
         }
     }
+
+    /// Returns a raw pointer to the locked data, valid for reads and writes
+    /// while the guard is held.
+    ///
+    /// This is an associated function, so that it does not shadow a method
+    /// of the data.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{RwLock, RwLockWriteGuard};
+    ///
+    /// let lock = RwLock::new(0u32);
+    ///
+    /// let guard = lock.write();
+    /// // SAFETY: The guard is held and not otherwise used meanwhile.
+    /// unsafe { RwLockWriteGuard::data_ptr(&guard).write(5) };
+    /// drop(guard);
+    ///
+    /// assert_eq!(*lock.read(), 5);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn data_ptr(this: &Self) -> *mut T {
+        this.lock.data.get()
+    }
+
+    /// Registers `f` to run on the protected data just before the write lock
+    /// is released.
+    ///
+    /// This is useful for work that must happen exactly once per critical
+    /// section, such as publishing a dirty flag, regardless of which path
+    /// the critical section leaves through.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{RwLock, RwLockWriteGuard};
+    ///
+    /// let lock = RwLock::new((0, false));
+    ///
+    /// {
+    ///     let mut guard = RwLockWriteGuard::on_unlock(lock.write(), |data| data.1 = true);
+    ///     guard.0 += 1;
+    ///     assert!(!guard.1);
+    /// }
+    ///
+    /// assert_eq!(*lock.read(), (1, true));
+    /// ```
+    pub const fn on_unlock<F>(this: Self, f: F) -> RwLockOnUnlockGuard<'rw, T, F, P>
+    where
+        F: FnOnce(&mut T),
+    {
+        RwLockOnUnlockGuard {
+            guard: this,
+            hook: ManuallyDrop::new(f),
+        }
+    }
+
+    /// Downgrades the lock to shared read access while `f` runs, letting
+    /// other readers in, and restores write access afterwards.
+    ///
+    /// The downgrade happens in one step, so no writer can get in between.
+    /// Write access is restored once the readers that got in meanwhile have
+    /// left, even if `f` panics. No other writer can get in until then.
+    ///
+    /// `f` must not write-lock the same lock, which would deadlock, nor read
+    /// it with [`RwLock::read`] if writers may be waiting; use
+    /// [`RwLock::read_recursive`] instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{RwLock, RwLockWriteGuard};
+    ///
+    /// let lock = RwLock::new(vec![3, 1, 2]);
+    ///
+    /// let mut guard = lock.write();
+    /// guard.sort_unstable();
+    ///
+    /// // Other readers may check the result while we verify it.
+    /// let sorted = RwLockWriteGuard::with_downgraded(&mut guard, |data| {
+    ///     assert_eq!(*lock.try_read().unwrap(), [1, 2, 3]);
+    ///     data.is_sorted()
+    /// });
+    ///
+    /// assert!(sorted);
+    /// guard.push(4);
+    /// assert!(lock.try_read().is_none());
+    /// ```
+    pub fn with_downgraded<R, F>(this: &mut Self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        downgrade_write(&this.lock.lock);
+        let _upgrade = Upgrade(this.lock);
+
+        // SAFETY: A read lock is held, so there is no writer.
+        f(unsafe { &*this.lock.data.get() })
+    }
+
+    /// Downgrades the lock to shared read access and makes a
+    /// [`MappedRwLockReadGuard`] for a component of the data, in one step.
+    ///
+    /// No writer can get in between, so the component is read exactly as it
+    /// was written. If `f` panics, the read lock is released.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{RwLock, RwLockWriteGuard};
+    ///
+    /// let lock = RwLock::new((0, 'a'));
+    ///
+    /// let mut guard = lock.write();
+    /// guard.1 = 'b';
+    ///
+    /// let guard = RwLockWriteGuard::downgrade_map(guard, |data| &data.1);
+    /// assert_eq!(*guard, 'b');
+    /// assert_eq!(*lock.try_read().unwrap(), (0, 'b'));
+    /// assert!(lock.try_write().is_none());
+    /// ```
+    pub fn downgrade_map<U: ?Sized, F>(this: Self, f: F) -> MappedRwLockReadGuard<'rw, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let lock = this.lock;
+        core::mem::forget(this);
+
+        downgrade_write(&lock.lock);
+        RwLockReadGuard::map(RwLockReadGuard::new(lock), f)
+    }
+
+    /// Makes a [`MappedRwLockWriteGuard`] for a component of the locked data.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::{RwLock, RwLockWriteGuard};
+    ///
+    /// let lock = RwLock::new((0, 'a'));
+    ///
+    /// *RwLockWriteGuard::map(lock.write(), |data| &mut data.1) = 'b';
+    /// assert_eq!(*lock.read(), (0, 'b'));
+    /// ```
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedRwLockWriteGuard<'rw, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let lock = this.lock;
+        core::mem::forget(this);
+
+        // SAFETY: The write lock is held, giving us exclusive access.
+        let data = NonNull::from(f(unsafe { &mut *lock.data.get() }));
+        MappedRwLockWriteGuard::new(&lock.lock, data)
+    }
+
+    /// Attempts to make a [`MappedRwLockWriteGuard`] for a component of the
+    /// locked data, handing back the original guard if `f` returns [`None`].
+    ///
+    /// # Errors
+    /// Returns the original guard if `f` returns [`None`].
+    pub fn try_map<U: ?Sized, F>(this: Self, f: F) -> Result<MappedRwLockWriteGuard<'rw, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        // SAFETY: The write lock is held, giving us exclusive access.
+        match f(unsafe { &mut *this.lock.data.get() }) {
+            Some(data) => {
+                let data = NonNull::from(data);
+                let lock = this.lock;
+                core::mem::forget(this);
+                Ok(MappedRwLockWriteGuard::new(&lock.lock, data))
+            }
+            None => Err(this),
+        }
+    }
 }
 
-impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
+impl<T: ?Sized, P: RwLockPolicy> Deref for RwLockWriteGuard<'_, T, P> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
+        // SAFETY: The write lock is held, giving us exclusive access.
         unsafe { &*self.lock.data.get() }
     }
 }
 
-impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+impl<T: ?Sized, P: RwLockPolicy> DerefMut for RwLockWriteGuard<'_, T, P> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The write lock is held, giving us exclusive access.
         unsafe { &mut *self.lock.data.get() }
     }
 }
 
-impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
+impl<T: ?Sized, P: RwLockPolicy> Drop for RwLockWriteGuard<'_, T, P> {
+    #[inline]
     fn drop(&mut self) {
-        todo!()
+        unlock_write(&self.lock.lock);
     }
 }
 
-impl<T: ?Sized + Debug> Debug for RwLockWriteGuard<'_, T> {
+impl<T: ?Sized + Debug, P: RwLockPolicy> Debug for RwLockWriteGuard<'_, T, P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         (**self).fmt(f)
     }
 }
 
-impl<T: ?Sized + Display> Display for RwLockWriteGuard<'_, T> {
+impl<T: ?Sized + Display, P: RwLockPolicy> Display for RwLockWriteGuard<'_, T, P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         (**self).fmt(f)
     }
 }
+
+/// An [`RwLockWriteGuard`] that runs a hook on the protected data just
+/// before the write lock is released.
+///
+/// This structure is created by the [`on_unlock`] method on
+/// [`RwLockWriteGuard`].
+///
+/// [`on_unlock`]: RwLockWriteGuard::on_unlock
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this lock while it is held"
+)]
+pub struct RwLockOnUnlockGuard<
+    'rw,
+    T: ?Sized,
+    F: FnOnce(&mut T),
+    P: RwLockPolicy = WriterPreferring,
+> {
+    guard: RwLockWriteGuard<'rw, T, P>,
+    hook: ManuallyDrop<F>,
+}
+
+impl<T: ?Sized, F: FnOnce(&mut T), P: RwLockPolicy> Deref for RwLockOnUnlockGuard<'_, T, F, P> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<T: ?Sized, F: FnOnce(&mut T), P: RwLockPolicy> DerefMut for RwLockOnUnlockGuard<'_, T, F, P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<T: ?Sized, F: FnOnce(&mut T), P: RwLockPolicy> Drop for RwLockOnUnlockGuard<'_, T, F, P> {
+    fn drop(&mut self) {
+        // SAFETY: The hook is taken exactly once, here.
+        let hook = unsafe { ManuallyDrop::take(&mut self.hook) };
+        hook(&mut self.guard);
+        // The inner guard is dropped afterwards, releasing the lock.
+    }
+}
+
+impl<T: ?Sized + Debug, F: FnOnce(&mut T), P: RwLockPolicy> Debug
+    for RwLockOnUnlockGuard<'_, T, F, P>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display, F: FnOnce(&mut T), P: RwLockPolicy> Display
+    for RwLockOnUnlockGuard<'_, T, F, P>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// An RAII read guard returned by [`RwLockReadGuard::map`], which can point
+/// to a component of the protected data. The read lock is released when this
+/// guard is dropped.
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "writers spin on this lock while it is held"
+)]
+pub struct MappedRwLockReadGuard<'rw, T: ?Sized> {
+    lock: &'rw AtomicUsize,
+    data: NonNull<T>,
+    phantom: core::marker::PhantomData<(&'rw T, *const ())>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for MappedRwLockReadGuard<'_, T> {}
+
+impl<'rw, T: ?Sized> MappedRwLockReadGuard<'rw, T> {
+    const fn new(lock: &'rw AtomicUsize, data: NonNull<T>) -> Self {
+        Self {
+            lock,
+            data,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns a raw pointer to the mapped data, valid for reads while the
+    /// guard is held.
+    #[inline]
+    #[must_use]
+    pub const fn data_ptr(this: &Self) -> *const T {
+        this.data.as_ptr()
+    }
+
+    /// Makes a [`MappedRwLockReadGuard`] for a further component of the data.
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedRwLockReadGuard<'rw, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let (lock, data) = (this.lock, this.data);
+        core::mem::forget(this);
+
+        // SAFETY: A read lock is held, so there is no writer.
+        let data = NonNull::from(f(unsafe { data.as_ref() }));
+        MappedRwLockReadGuard::new(lock, data)
+    }
+
+    /// Attempts to make a [`MappedRwLockReadGuard`] for a further component
+    /// of the data, handing back the original guard if `f` returns [`None`].
+    ///
+    /// # Errors
+    /// Returns the original guard if `f` returns [`None`].
+    pub fn try_map<U: ?Sized, F>(this: Self, f: F) -> Result<MappedRwLockReadGuard<'rw, U>, Self>
+    where
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        // SAFETY: A read lock is held, so there is no writer.
+        match f(unsafe { this.data.as_ref() }) {
+            Some(data) => {
+                let data = NonNull::from(data);
+                let lock = this.lock;
+                core::mem::forget(this);
+                Ok(MappedRwLockReadGuard::new(lock, data))
+            }
+            None => Err(this),
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for MappedRwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: A read lock is held, so there is no writer.
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T: ?Sized> Drop for MappedRwLockReadGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        unlock_read(self.lock);
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for MappedRwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for MappedRwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// An RAII write guard returned by [`RwLockWriteGuard::map`], which can
+/// point to a component of the protected data. The write lock is released
+/// when this guard is dropped.
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this lock while it is held"
+)]
+pub struct MappedRwLockWriteGuard<'rw, T: ?Sized> {
+    lock: &'rw AtomicUsize,
+    data: NonNull<T>,
+    phantom: core::marker::PhantomData<(&'rw mut T, *const ())>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for MappedRwLockWriteGuard<'_, T> {}
+
+impl<'rw, T: ?Sized> MappedRwLockWriteGuard<'rw, T> {
+    const fn new(lock: &'rw AtomicUsize, data: NonNull<T>) -> Self {
+        Self {
+            lock,
+            data,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns a raw pointer to the mapped data, valid for reads and writes
+    /// while the guard is held.
+    #[inline]
+    #[must_use]
+    pub const fn data_ptr(this: &Self) -> *mut T {
+        this.data.as_ptr()
+    }
+
+    /// Makes a [`MappedRwLockWriteGuard`] for a further component of the
+    /// data.
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedRwLockWriteGuard<'rw, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let (lock, mut data) = (this.lock, this.data);
+        core::mem::forget(this);
+
+        // SAFETY: The write lock is held, giving us exclusive access.
+        let data = NonNull::from(f(unsafe { data.as_mut() }));
+        MappedRwLockWriteGuard::new(lock, data)
+    }
+
+    /// Attempts to make a [`MappedRwLockWriteGuard`] for a further component
+    /// of the data, handing back the original guard if `f` returns [`None`].
+    ///
+    /// # Errors
+    /// Returns the original guard if `f` returns [`None`].
+    pub fn try_map<U: ?Sized, F>(
+        mut this: Self,
+        f: F,
+    ) -> Result<MappedRwLockWriteGuard<'rw, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        // SAFETY: The write lock is held, giving us exclusive access.
+        match f(unsafe { this.data.as_mut() }) {
+            Some(data) => {
+                let data = NonNull::from(data);
+                let lock = this.lock;
+                core::mem::forget(this);
+                Ok(MappedRwLockWriteGuard::new(lock, data))
+            }
+            None => Err(this),
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for MappedRwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The write lock is held, giving us exclusive access.
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MappedRwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The write lock is held, giving us exclusive access.
+        unsafe { self.data.as_mut() }
+    }
+}
+
+impl<T: ?Sized> Drop for MappedRwLockWriteGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        unlock_write(self.lock);
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for MappedRwLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for MappedRwLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(feature = "lock_api")]
+unsafe impl<P: RwLockPolicy> lock_api::RawRwLock for RwLock<(), P> {
+    const INIT: Self = Self {
+        lock: AtomicUsize::new(0),
+        queue: P::QUEUE,
+        data: UnsafeCell::new(()),
+    };
+
+    type GuardMarker = lock_api::GuardSend;
+
+    fn lock_shared(&self) {
+        core::mem::forget(self.read());
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        self.try_read().map(core::mem::forget).is_some()
+    }
+
+    unsafe fn unlock_shared(&self) {
+        unlock_read(&self.lock);
+    }
+
+    fn lock_exclusive(&self) {
+        core::mem::forget(self.write());
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        self.try_write().map(core::mem::forget).is_some()
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        unlock_write(&self.lock);
+    }
+
+    fn is_locked(&self) -> bool {
+        Self::is_locked(self)
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        Self::is_locked_exclusive(self)
+    }
+}
+
+#[cfg(feature = "lock_api")]
+unsafe impl<P: RwLockPolicy> lock_api::RawRwLockRecursive for RwLock<(), P> {
+    fn lock_shared_recursive(&self) {
+        core::mem::forget(self.read_recursive());
+    }
+
+    fn try_lock_shared_recursive(&self) -> bool {
+        self.try_read_recursive().map(core::mem::forget).is_some()
+    }
+}
+
+#[cfg(all(feature = "lock_api", feature = "std"))]
+unsafe impl<P: RwLockPolicy> lock_api::RawRwLockTimed for RwLock<(), P> {
+    type Duration = Duration;
+    type Instant = Instant;
+
+    fn try_lock_shared_for(&self, timeout: Duration) -> bool {
+        self.try_read_for(timeout).map(core::mem::forget).is_some()
+    }
+
+    fn try_lock_shared_until(&self, deadline: Instant) -> bool {
+        self.try_read_until(deadline)
+            .map(core::mem::forget)
+            .is_some()
+    }
+
+    fn try_lock_exclusive_for(&self, timeout: Duration) -> bool {
+        self.try_write_for(timeout).map(core::mem::forget).is_some()
+    }
+
+    fn try_lock_exclusive_until(&self, deadline: Instant) -> bool {
+        self.try_write_until(deadline)
+            .map(core::mem::forget)
+            .is_some()
+    }
+}
+
+#[cfg(all(feature = "lock_api", feature = "std"))]
+unsafe impl<P: RwLockPolicy> lock_api::RawRwLockRecursiveTimed for RwLock<(), P> {
+    fn try_lock_shared_recursive_for(&self, timeout: Duration) -> bool {
+        self.try_lock_shared_recursive_until(Deadline::after(timeout).instant())
+    }
+
+    fn try_lock_shared_recursive_until(&self, deadline: Instant) -> bool {
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Some(guard) = self.try_read_recursive() {
+                core::mem::forget(guard);
+                return true;
+            }
+
+            if Instant::now() >= deadline {
+                return false;
+            }
+
+            backoff.relax();
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use sealed::Tickets;
+
+/// Decides the order in which an [`RwLock`] admits contending readers and
+/// writers.
+///
+/// The policy is a type parameter of the lock, so a lock only pays for the
+/// bookkeeping its policy needs. It is one of [`ReaderPreferring`],
+/// [`WriterPreferring`] and [`Fifo`], and cannot be implemented outside of
+/// this crate.
+///
+/// [`RwLock`]: crate::sync::RwLock
+pub trait RwLockPolicy: sealed::Policy + Copy {}
+
+/// Admit readers whenever no writer holds the lock. This gives readers the
+/// highest throughput, but a steady stream of them starves writers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct ReaderPreferring;
+
+/// Keep new readers out while a writer is waiting. A steady stream of writers
+/// can starve readers. This is the default policy of an [`RwLock`].
+///
+/// [`RwLock`]: crate::sync::RwLock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct WriterPreferring;
+
+/// Admit readers and writers in the order they arrived, with consecutive
+/// readers sharing the lock.
+///
+/// Nobody starves, at the cost of two ticket counters per lock and an extra
+/// update of them per acquisition. Timed and `try_` acquisitions only succeed
+/// if nobody is queued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Fifo;
+
+impl RwLockPolicy for ReaderPreferring {}
+impl RwLockPolicy for WriterPreferring {}
+impl RwLockPolicy for Fifo {}
+
+// Not `pub`, which would export it from `skirt::sync` along with the
+// policies, and let other crates implement them.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) mod sealed {
+    use crate::sync::atomic::AtomicUsize;
+
+    /// What an [`RwLockPolicy`](super::RwLockPolicy) does, kept out of the
+    /// public API.
+    pub trait Policy: Sized {
+        /// The state the policy keeps in each lock.
+        type Queue: Send + Sync;
+
+        /// The state of a new lock.
+        #[allow(clippy::declare_interior_mutable_const)]
+        const QUEUE: Self::Queue;
+
+        /// Whether a waiting writer keeps new readers out.
+        const PREFER_WRITERS: bool;
+
+        /// Waits for the caller's turn, runs `acquire`, and passes the turn
+        /// on once the lock is held.
+        fn in_turn<G>(queue: &Self::Queue, acquire: impl FnOnce() -> G) -> G;
+
+        /// Runs `acquire` in a turn if nobody is queued, passing the turn on
+        /// whether or not it succeeds.
+        fn try_in_turn<G>(queue: &Self::Queue, acquire: impl FnOnce() -> Option<G>) -> Option<G>;
+
+        /// Empties the queue, forgetting anyone who was waiting in it.
+        fn reset(queue: &Self::Queue);
+    }
+
+    /// The ticket counters of a lock under the [`Fifo`](super::Fifo) policy.
+    pub struct Tickets {
+        pub(super) next: AtomicUsize,
+        pub(super) serving: AtomicUsize,
+    }
+}
+
+impl sealed::Policy for ReaderPreferring {
+    type Queue = ();
+
+    const QUEUE: Self::Queue = ();
+    const PREFER_WRITERS: bool = false;
+
+    #[inline]
+    fn in_turn<G>((): &(), acquire: impl FnOnce() -> G) -> G {
+        acquire()
+    }
+
+    #[inline]
+    fn try_in_turn<G>((): &(), acquire: impl FnOnce() -> Option<G>) -> Option<G> {
+        acquire()
+    }
+
+    #[inline]
+    fn reset((): &()) {}
+}
+
+impl sealed::Policy for WriterPreferring {
+    type Queue = ();
+
+    const QUEUE: Self::Queue = ();
+    const PREFER_WRITERS: bool = true;
+
+    #[inline]
+    fn in_turn<G>((): &(), acquire: impl FnOnce() -> G) -> G {
+        acquire()
+    }
+
+    #[inline]
+    fn try_in_turn<G>((): &(), acquire: impl FnOnce() -> Option<G>) -> Option<G> {
+        acquire()
+    }
+
+    #[inline]
+    fn reset((): &()) {}
+}
+
+impl sealed::Policy for Fifo {
+    type Queue = Tickets;
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const QUEUE: Self::Queue = Tickets {
+        next: AtomicUsize::new(0),
+        serving: AtomicUsize::new(0),
+    };
+    const PREFER_WRITERS: bool = false;
+
+    // Consecutive readers share the lock, since each passes the turn on as
+    // soon as it is inside, while a writer keeps everyone behind it waiting.
+    fn in_turn<G>(queue: &Tickets, acquire: impl FnOnce() -> G) -> G {
+        let ticket = queue.next.fetch_add(1, Ordering::Relaxed);
+        let mut backoff = Backoff::new();
+
+        while queue.serving.load(Ordering::Acquire) != ticket {
+            backoff.relax();
+        }
+
+        let guard = acquire();
+        queue.serving.fetch_add(1, Ordering::Release);
+        guard
+    }
+
+    fn try_in_turn<G>(queue: &Tickets, acquire: impl FnOnce() -> Option<G>) -> Option<G> {
+        let serving = queue.serving.load(Ordering::Acquire);
+
+        queue
+            .next
+            .compare_exchange(
+                serving,
+                serving.wrapping_add(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .ok()?;
+
+        let guard = acquire();
+        queue.serving.fetch_add(1, Ordering::Release);
+        guard
+    }
+
+    fn reset(queue: &Tickets) {
+        queue.serving.store(0, Ordering::Release);
+        queue.next.store(0, Ordering::Release);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::cache_padded::{CachePadded, current_shard};
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A counter sharded over `SHARDS` cache lines, for counts updated far more
+/// often than they are read.
+///
+/// Each update only touches the shard picked by the [`current_context`]
+/// token of the updating context, so contexts on different cores do not
+/// contend on a single cache line. Reading the total with [`sum`] visits
+/// every shard.
+///
+/// Updates wrap around on overflow, so a counter that is both incremented
+/// and decremented always sums to the right value, as long as the true
+/// total fits in a `usize`.
+///
+/// Without a context (a `no_std` target with no registered
+/// [`CurrentContext`]), every update uses the same shard.
+///
+/// [`sum`]: Self::sum
+/// [`current_context`]: crate::sync::current_context
+/// [`CurrentContext`]: crate::sync::CurrentContext
+///
+/// # Examples
+/// ```
+/// use skirt::sync::ShardedCounter;
+///
+/// static REQUESTS: ShardedCounter = ShardedCounter::new();
+///
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             for _ in 0..1000 {
+///                 REQUESTS.add(1);
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(REQUESTS.sum(), 4000);
+/// ```
+pub struct ShardedCounter<const SHARDS: usize = 8> {
+    shards: [CachePadded<AtomicUsize>; SHARDS],
+}
+
+impl<const SHARDS: usize> ShardedCounter<SHARDS> {
+    /// Creates a new counter starting at zero.
+    ///
+    /// # Panics
+    /// Panics if `SHARDS` is zero.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        assert!(SHARDS > 0, "a ShardedCounter needs at least one shard");
+
+        Self {
+            shards: [const { CachePadded::new(AtomicUsize::new(0)) }; SHARDS],
+        }
+    }
+
+    /// Adds `n` to the counter.
+    #[inline]
+    pub fn add(&self, n: usize) {
+        self.shard().fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Subtracts `n` from the counter.
+    #[inline]
+    pub fn sub(&self, n: usize) {
+        self.shard().fetch_sub(n, Ordering::Relaxed);
+    }
+
+    /// Returns the total of the counter.
+    ///
+    /// The shards are read one after another, so updates made concurrently
+    /// with this call may or may not be included.
+    #[must_use]
+    pub fn sum(&self) -> usize {
+        self.shards.iter().fold(0, |sum, shard| {
+            sum.wrapping_add(shard.load(Ordering::Relaxed))
+        })
+    }
+
+    /// Resets the counter to zero, returning its previous total.
+    ///
+    /// # Examples
+    /// ```
+    /// use skirt::sync::ShardedCounter;
+    ///
+    /// let mut counter = ShardedCounter::<4>::new();
+    /// counter.add(3);
+    ///
+    /// assert_eq!(counter.reset(), 3);
+    /// assert_eq!(counter.sum(), 0);
+    /// ```
+    pub fn reset(&mut self) -> usize {
+        self.shards.iter_mut().fold(0, |sum, shard| {
+            sum.wrapping_add(core::mem::take(shard.get_mut()))
+        })
+    }
+
+    fn shard(&self) -> &AtomicUsize {
+        &self.shards[current_shard(SHARDS)]
+    }
+}
+
+impl<const SHARDS: usize> Default for ShardedCounter<SHARDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SHARDS: usize> Debug for ShardedCounter<SHARDS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ShardedCounter").field(&self.sum()).finish()
+    }
+}
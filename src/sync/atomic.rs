@@ -1,4 +1,8 @@
+#[cfg(all(feature = "std", not(feature = "portable")))]
+pub use core::sync::atomic::AtomicU64;
 #[cfg(not(feature = "portable"))]
-pub use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+pub use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering, fence};
+#[cfg(all(feature = "std", feature = "portable"))]
+pub use portable_atomic::AtomicU64;
 #[cfg(feature = "portable")]
-pub use portable_atomic::{AtomicBool, AtomicU8, Ordering};
+pub use portable_atomic::{AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering, fence};
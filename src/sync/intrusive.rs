@@ -0,0 +1,176 @@
+//! Intrusive data structures, whose nodes are provided and owned by the user.
+//!
+//! These are building blocks for wait queues, observer registries and free
+//! lists that cannot allocate, such as in kernels and firmware.
+
+/// The atomic pointer that links nodes, which comes from `portable-atomic`
+/// with the `portable` feature. Nodes should use this re-export, so that they
+/// build with either.
+pub use crate::sync::atomic::AtomicPtr;
+use crate::sync::atomic::Ordering;
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::ptr::{self, NonNull};
+
+/// A node that can be linked into an [`AtomicList`].
+///
+/// # Examples
+/// ```
+/// use skirt::sync::intrusive::{AtomicPtr, Linked};
+///
+/// struct Observer {
+///     id: u32,
+///     next: AtomicPtr<Observer>,
+/// }
+///
+/// impl Linked for Observer {
+///     fn next(&self) -> &AtomicPtr<Self> {
+///         &self.next
+///     }
+/// }
+/// ```
+pub trait Linked: Sized {
+    /// Returns the pointer to the next node, which the list owns while this
+    /// node is linked.
+    fn next(&self) -> &AtomicPtr<Self>;
+}
+
+/// A lock-free, intrusive, singly linked list with push and drain
+/// operations.
+///
+/// Any number of contexts may [`push`] nodes concurrently, and [`drain`]
+/// atomically takes every node pushed so far, yielding the most recently
+/// pushed first. Since nodes are never removed one by one, the list is not
+/// subject to the ABA problem.
+///
+/// [`push`]: Self::push
+/// [`drain`]: Self::drain
+///
+/// # Examples
+/// ```
+/// use skirt::sync::intrusive::{AtomicList, AtomicPtr, Linked};
+///
+/// struct Node {
+///     value: u32,
+///     next: AtomicPtr<Node>,
+/// }
+///
+/// impl Linked for Node {
+///     fn next(&self) -> &AtomicPtr<Self> {
+///         &self.next
+///     }
+/// }
+///
+/// let nodes = [1, 2, 3].map(|value| Node { value, next: AtomicPtr::default() });
+/// let list = AtomicList::new();
+///
+/// for node in &nodes {
+///     // SAFETY: Each node is pushed once and outlives the list.
+///     unsafe { list.push(node) };
+/// }
+///
+/// let values: Vec<u32> = list.drain().map(|node| node.value).collect();
+/// assert_eq!(values, [3, 2, 1]);
+/// assert!(list.is_empty());
+/// ```
+pub struct AtomicList<'a, T: Linked> {
+    head: AtomicPtr<T>,
+    phantom: PhantomData<&'a T>,
+}
+
+unsafe impl<T: Linked + Sync> Send for AtomicList<'_, T> {}
+unsafe impl<T: Linked + Sync> Sync for AtomicList<'_, T> {}
+
+impl<'a, T: Linked> AtomicList<'a, T> {
+    /// Creates a new, empty list.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Pushes `node` onto the front of the list.
+    ///
+    /// # Safety
+    /// `node` must not currently be linked into this or any other list. It
+    /// may be pushed again once it has been yielded by a [`Drain`].
+    pub unsafe fn push(&self, node: &'a T) {
+        let node_ptr = ptr::from_ref(node).cast_mut();
+        let mut head = self.head.load(Ordering::Relaxed);
+
+        loop {
+            node.next().store(head, Ordering::Relaxed);
+
+            match self.head.compare_exchange_weak(
+                head,
+                node_ptr,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Takes every node out of the list, returning an iterator over them
+    /// from the most recently pushed to the least.
+    pub fn drain(&self) -> Drain<'a, T> {
+        Drain {
+            next: NonNull::new(self.head.swap(ptr::null_mut(), Ordering::Acquire)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns whether the list is empty.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Relaxed).is_null()
+    }
+}
+
+impl<T: Linked> Default for AtomicList<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Linked> Debug for AtomicList<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AtomicList")
+            .field("empty", &self.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over the nodes taken out of an [`AtomicList`].
+///
+/// This is created by [`AtomicList::drain`]. Each node is unlinked before it
+/// is yielded, so it may be pushed again right away.
+pub struct Drain<'a, T: Linked> {
+    next: Option<NonNull<T>>,
+    phantom: PhantomData<&'a T>,
+}
+
+impl<'a, T: Linked> Iterator for Drain<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: Pushed nodes live for `'a`, and the drain owns the links
+        // of the nodes it has not yielded yet.
+        let node = unsafe { self.next?.as_ref() };
+        self.next = NonNull::new(node.next().swap(ptr::null_mut(), Ordering::Relaxed));
+        Some(node)
+    }
+}
+
+impl<T: Linked> Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Drain").finish_non_exhaustive()
+    }
+}
@@ -1,6 +1,57 @@
 pub(crate) mod atomic;
+pub mod intrusive;
+pub mod slots;
 
+#[cfg(feature = "std")]
+pub use crate::atomic_instant::*;
+pub use crate::atomic_waker::*;
+pub use crate::biased_rwlock::*;
+pub use crate::bitset::*;
+pub use crate::blocking_guard::*;
+pub use crate::brlock::*;
+pub use crate::completion::*;
+pub use crate::context::*;
+#[cfg(feature = "alloc")]
+pub use crate::cow_lock::*;
+#[cfg(feature = "std")]
+pub use crate::deadline::*;
+pub use crate::error::*;
+pub use crate::exclusive_once::*;
+pub use crate::fair_rwlock::*;
+pub use crate::flag::*;
+pub use crate::global_slot::*;
+pub use crate::guarded::*;
+pub use crate::id::*;
+pub use crate::init_flag::*;
+#[cfg(feature = "alloc")]
+pub use crate::interner::*;
+pub use crate::isr_queue::*;
+pub use crate::isr_rwlock::*;
 pub use crate::lazy_lock::*;
+pub use crate::lazy_with::*;
+pub use crate::leak_checked::*;
+pub use crate::local_mutex::*;
+pub use crate::lock_all::*;
+pub use crate::lock_array::*;
 pub use crate::mutex::*;
 pub use crate::once::*;
+pub use crate::once_group::*;
 pub use crate::once_lock::*;
+pub use crate::once_per_key::*;
+pub use crate::periodic::*;
+pub use crate::pool::*;
+pub use crate::priority_mutex::*;
+pub use crate::raw_byte_lock::*;
+pub use crate::register_lock::*;
+pub use crate::relax_strategy::*;
+pub use crate::retry_lazy::*;
+pub use crate::rwlock::*;
+pub use crate::rwlock_policy::*;
+pub use crate::sharded_counter::*;
+#[cfg(feature = "std")]
+pub use crate::thread_pool::*;
+#[cfg(feature = "std")]
+pub use crate::timed_hold::*;
+pub use crate::triple_buffer::*;
+pub use crate::wait_map::*;
+pub use crate::work_queue::*;
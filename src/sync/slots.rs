@@ -0,0 +1,167 @@
+//! Global slots for the handlers nearly every firmware installs once at
+//! startup and reads from anywhere, including interrupt handlers.
+//!
+//! Each slot is a [`GlobalSlot`], so installing twice is reported with the
+//! location of the first installation, and reading is a single atomic load
+//! that never waits.
+
+use crate::global_slot::{GlobalSlot, InstallError};
+use core::fmt::{Arguments, Debug, Formatter};
+use core::panic::PanicInfo;
+
+/// A sink for log messages, installed into [`LOGGER`].
+///
+/// Loggers may be called from interrupt handlers, and from several contexts
+/// at once.
+pub trait Log: Sync {
+    /// Writes a formatted message.
+    fn log(&self, args: Arguments<'_>);
+}
+
+/// A function reporting a panic, installed into [`PANIC_HOOK`].
+pub type PanicHook = fn(&PanicInfo<'_>);
+
+/// The process-wide logger.
+///
+/// # Examples
+/// ```
+/// use core::fmt::Arguments;
+/// use skirt::sync::slots::{LOGGER, Log};
+///
+/// struct Serial;
+///
+/// impl Log for Serial {
+///     fn log(&self, args: Arguments<'_>) {
+///         println!("{args}");
+///     }
+/// }
+///
+/// assert!(!LOGGER.log(format_args!("dropped")));
+///
+/// LOGGER.set(&Serial).unwrap();
+/// assert!(LOGGER.log(format_args!("booted in {} ms", 12)));
+/// ```
+pub static LOGGER: LoggerSlot = LoggerSlot::new();
+
+/// The process-wide panic hook, for a `#[panic_handler]` to report through.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::slots::PANIC_HOOK;
+///
+/// PANIC_HOOK.set(|info| eprintln!("fatal: {info}")).unwrap();
+/// assert!(PANIC_HOOK.get().is_some());
+/// ```
+pub static PANIC_HOOK: PanicHookSlot = PanicHookSlot::new();
+
+/// A slot holding a [`Log`] implementation.
+///
+/// The process-wide instance is [`LOGGER`].
+pub struct LoggerSlot {
+    slot: GlobalSlot<&'static dyn Log>,
+}
+
+impl LoggerSlot {
+    /// Creates a new, empty slot.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slot: GlobalSlot::new("logger"),
+        }
+    }
+
+    /// Installs `logger` into the slot.
+    ///
+    /// # Errors
+    /// Returns an [`InstallError`] holding `logger` if a logger was already
+    /// installed.
+    #[track_caller]
+    pub fn set(&self, logger: &'static dyn Log) -> Result<(), InstallError<&'static dyn Log>> {
+        self.slot.install(logger).map(|_| ())
+    }
+
+    /// Returns the installed logger, or [`None`] if none was installed yet.
+    ///
+    /// This is safe to call from interrupt context.
+    #[inline]
+    pub fn get(&self) -> Option<&'static dyn Log> {
+        self.slot.get().copied()
+    }
+
+    /// Writes a message to the installed logger, returning whether one was
+    /// installed.
+    pub fn log(&self, args: Arguments<'_>) -> bool {
+        self.get().map(|logger| logger.log(args)).is_some()
+    }
+}
+
+impl Default for LoggerSlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for LoggerSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LoggerSlot")
+            .field("installed", &self.get().is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A slot holding a [`PanicHook`].
+///
+/// The process-wide instance is [`PANIC_HOOK`].
+pub struct PanicHookSlot {
+    slot: GlobalSlot<PanicHook>,
+}
+
+impl PanicHookSlot {
+    /// Creates a new, empty slot.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slot: GlobalSlot::new("panic hook"),
+        }
+    }
+
+    /// Installs `hook` into the slot.
+    ///
+    /// # Errors
+    /// Returns an [`InstallError`] holding `hook` if a hook was already
+    /// installed.
+    #[track_caller]
+    pub fn set(&self, hook: PanicHook) -> Result<(), InstallError<PanicHook>> {
+        self.slot.install(hook).map(|_| ())
+    }
+
+    /// Returns the installed hook, or [`None`] if none was installed yet.
+    ///
+    /// This is safe to call from interrupt context.
+    #[inline]
+    pub fn get(&self) -> Option<PanicHook> {
+        self.slot.get().copied()
+    }
+
+    /// Reports `info` to the installed hook, returning whether one was
+    /// installed.
+    pub fn call(&self, info: &PanicInfo<'_>) -> bool {
+        self.get().map(|hook| hook(info)).is_some()
+    }
+}
+
+impl Default for PanicHookSlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for PanicHookSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PanicHookSlot")
+            .field("installed", &self.get().is_some())
+            .finish_non_exhaustive()
+    }
+}
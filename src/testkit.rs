@@ -0,0 +1,263 @@
+//! Stress harnesses for validating lock implementations.
+//!
+//! A [`LockTester`] runs a number of threads against a lock for a while,
+//! checking an invariant that only holds if the lock provides mutual
+//! exclusion. The lock is reached through closures returning its guards, so
+//! the same harness covers every lock in this crate as well as new backends
+//! built on top of it.
+//!
+//! The data behind the lock is a [`Pair`] of counters that writers increment
+//! one after the other, and that readers check to be equal.
+//!
+//! # Examples
+//! ```
+//! use skirt::sync::{Mutex, RwLock};
+//! use skirt::testkit::{LockTester, Pair};
+//! use std::time::Duration;
+//!
+//! let tester = LockTester::new().threads(4).duration(Duration::from_millis(20));
+//!
+//! let mutex = Mutex::new(Pair::new());
+//! let report = tester.exclusive(|| mutex.lock());
+//! assert_eq!(mutex.lock().writes(), report.writes);
+//!
+//! let lock = RwLock::new(Pair::new());
+//! let report = tester.shared(|| lock.write(), || lock.read());
+//! assert_eq!(lock.read().writes(), report.writes);
+//! ```
+
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
+
+/// The data hammered by a [`LockTester`]: two counters that are equal
+/// whenever the lock is not held by a writer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pair {
+    first: usize,
+    second: usize,
+}
+
+impl Pair {
+    /// Creates a new pair with both counters at zero.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            first: 0,
+            second: 0,
+        }
+    }
+
+    /// Increments both counters, one after the other, leaving a window in
+    /// which they differ.
+    pub fn write(&mut self) {
+        self.first += 1;
+        core::hint::black_box(&mut *self);
+        core::hint::spin_loop();
+        self.second += 1;
+    }
+
+    /// Checks that the counters are equal.
+    ///
+    /// # Panics
+    /// Panics if they differ, which means a reader or writer got in while
+    /// another writer held the lock.
+    #[track_caller]
+    pub fn check(&self) {
+        let (first, second) = core::hint::black_box((self.first, self.second));
+
+        assert_eq!(
+            first, second,
+            "lock invariant violated: another thread got in during a write"
+        );
+    }
+
+    /// Returns the number of completed writes.
+    #[inline]
+    #[must_use]
+    pub const fn writes(&self) -> usize {
+        self.first
+    }
+}
+
+/// The number of operations a [`LockTester`] run performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Report {
+    /// The number of writes, each under an exclusive guard.
+    pub writes: usize,
+    /// The number of reads, each under a shared guard.
+    pub reads: usize,
+    /// How long the run took.
+    pub elapsed: Duration,
+}
+
+/// A configurable stress harness for locks.
+///
+/// Both harnesses panic if the invariant of the [`Pair`] is violated, or if
+/// the final number of writes in the pair does not match the number of
+/// writes performed.
+#[derive(Debug, Clone, Copy)]
+pub struct LockTester {
+    threads: usize,
+    duration: Duration,
+    reads_per_write: usize,
+}
+
+impl LockTester {
+    /// Creates a tester running one thread per available core for 100
+    /// milliseconds, with three reads per write in [`shared`] runs.
+    ///
+    /// [`shared`]: Self::shared
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            threads: std::thread::available_parallelism().map_or(4, usize::from),
+            duration: Duration::from_millis(100),
+            reads_per_write: 3,
+        }
+    }
+
+    /// Sets the number of threads.
+    ///
+    /// # Panics
+    /// Panics if `threads` is zero.
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "a LockTester needs at least one thread");
+        self.threads = threads;
+        self
+    }
+
+    /// Sets how long each run lasts.
+    #[must_use]
+    pub const fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets how many reads each thread performs between two writes in
+    /// [`shared`](Self::shared) runs.
+    #[must_use]
+    pub const fn reads_per_write(mut self, reads: usize) -> Self {
+        self.reads_per_write = reads;
+        self
+    }
+
+    /// Hammers a lock with writers only, where `lock` acquires it.
+    ///
+    /// # Panics
+    /// Panics if the lock lets two writers in at once.
+    pub fn exclusive<F, G>(&self, lock: F) -> Report
+    where
+        F: Fn() -> G + Sync,
+        G: DerefMut<Target = Pair>,
+    {
+        self.run(
+            |_| {
+                let mut guard = lock();
+                guard.check();
+                guard.write();
+                true
+            },
+            || lock().writes(),
+        )
+    }
+
+    /// Hammers a reader-writer lock with a mix of readers and writers, where
+    /// `write` and `read` acquire it.
+    ///
+    /// # Panics
+    /// Panics if the lock lets a reader or writer in alongside a writer.
+    pub fn shared<W, R, WG, RG>(&self, write: W, read: R) -> Report
+    where
+        W: Fn() -> WG + Sync,
+        R: Fn() -> RG + Sync,
+        WG: DerefMut<Target = Pair>,
+        RG: Deref<Target = Pair>,
+    {
+        let period = self.reads_per_write + 1;
+
+        self.run(
+            |iteration| {
+                if iteration % period == 0 {
+                    let mut guard = write();
+                    guard.check();
+                    guard.write();
+                    true
+                } else {
+                    read().check();
+                    false
+                }
+            },
+            || read().writes(),
+        )
+    }
+
+    /// Runs `operation` on every thread until the deadline, where it returns
+    /// whether it wrote, and checks the writes against `writes`.
+    fn run<O, W>(&self, operation: O, writes: W) -> Report
+    where
+        O: Fn(usize) -> bool + Sync,
+        W: Fn() -> usize,
+    {
+        let before = writes();
+        let start = Instant::now();
+        let deadline = start + self.duration;
+
+        let mut report = Report {
+            writes: 0,
+            reads: 0,
+            elapsed: Duration::ZERO,
+        };
+
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let (mut writes, mut reads) = (0, 0);
+
+                        for iteration in 0.. {
+                            if Instant::now() >= deadline {
+                                break;
+                            }
+
+                            if operation(iteration) {
+                                writes += 1;
+                            } else {
+                                reads += 1;
+                            }
+                        }
+
+                        (writes, reads)
+                    })
+                })
+                .collect();
+
+            for worker in workers {
+                let (writes, reads) = worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+
+                report.writes += writes;
+                report.reads += reads;
+            }
+        });
+
+        report.elapsed = start.elapsed();
+
+        assert_eq!(
+            writes() - before,
+            report.writes,
+            "lock invariant violated: writes were lost"
+        );
+
+        report
+    }
+}
+
+impl Default for LockTester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
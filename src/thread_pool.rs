@@ -0,0 +1,158 @@
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::work_queue::WorkQueue;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::thread::JoinHandle;
+
+/// The number of jobs that can be queued before [`ThreadPool::execute`]
+/// blocks.
+const QUEUE_LEN: usize = 64;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A fixed-size pool of worker threads.
+///
+/// Jobs passed to [`execute`] are queued on a [`WorkQueue`] and run by the
+/// first idle worker. [`join`] waits until every job submitted so far has
+/// finished, and dropping the pool lets the workers drain the queue and then
+/// joins them.
+///
+/// A job that panics does not take its worker down with it: the panic is
+/// reported by the panic hook as usual, and the worker moves on to the next
+/// job.
+///
+/// [`execute`]: Self::execute
+/// [`join`]: Self::join
+///
+/// # Examples
+/// ```
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+/// use skirt::sync::ThreadPool;
+/// use std::sync::Arc;
+///
+/// let pool = ThreadPool::new(4);
+/// let total = Arc::new(AtomicUsize::new(0));
+///
+/// for job in 1..=100 {
+///     let total = Arc::clone(&total);
+///     pool.execute(move || {
+///         total.fetch_add(job, Ordering::Relaxed);
+///     });
+/// }
+///
+/// pool.join();
+/// assert_eq!(total.load(Ordering::Relaxed), 5050);
+/// ```
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+struct Shared {
+    queue: WorkQueue<Job, QUEUE_LEN>,
+    pending: AtomicUsize,
+}
+
+impl ThreadPool {
+    /// Creates a new pool with `threads` worker threads.
+    ///
+    /// # Panics
+    /// Panics if `threads` is zero, or if a thread cannot be spawned.
+    #[must_use]
+    pub fn new(threads: usize) -> Self {
+        assert!(threads > 0, "a thread pool needs at least one thread");
+
+        let shared = Arc::new(Shared {
+            queue: WorkQueue::new(),
+            pending: AtomicUsize::new(0),
+        });
+
+        let workers = (0..threads)
+            .map(|index| {
+                let shared = Arc::clone(&shared);
+
+                std::thread::Builder::new()
+                    .name(std::format!("skirt-worker-{index}"))
+                    .spawn(move || shared.work())
+                    .expect("failed to spawn a worker thread")
+            })
+            .collect();
+
+        Self { shared, workers }
+    }
+
+    /// Queues `job` to be run by one of the workers, blocking while the
+    /// queue is full.
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.shared.pending.fetch_add(1, Ordering::Relaxed);
+
+        // The queue is only closed when the pool is dropped.
+        if self.shared.queue.push(Box::new(job)).is_err() {
+            unreachable!();
+        }
+    }
+
+    /// Blocks until every job submitted so far has finished.
+    ///
+    /// Jobs submitted by other threads while this waits are waited for too.
+    pub fn join(&self) {
+        let mut backoff = Backoff::new();
+
+        while self.shared.pending.load(Ordering::Acquire) != 0 {
+            backoff.relax();
+        }
+    }
+
+    /// Returns the number of worker threads.
+    #[inline]
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Returns the number of jobs that are queued or running.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.shared.pending.load(Ordering::Relaxed)
+    }
+}
+
+impl Shared {
+    fn work(&self) {
+        while let Some(job) = self.queue.pop() {
+            // The panic has already been reported by the hook, and the job's
+            // state is gone with it, so there is nothing left to unwind.
+            let _ = catch_unwind(AssertUnwindSafe(job));
+            self.pending.fetch_sub(1, Ordering::Release);
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shared.queue.close();
+
+        for worker in self.workers.drain(..) {
+            // Job panics are caught inside the worker, so it cannot fail.
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Debug for ThreadPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ThreadPool")
+            .field("threads", &self.threads())
+            .field("pending", &self.pending())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,175 @@
+use crate::mutex::{Mutex, MutexGuard};
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
+
+/// A [`Mutex`] that panics when a guard is held for longer than a bound.
+///
+/// Each guard records when it was acquired, and checks on release how long
+/// the lock was held. This catches critical sections that have grown too
+/// long, such as ones that accidentally perform I/O, in systems where every
+/// waiter spins for the whole hold time.
+///
+/// The check is skipped if the thread is already panicking.
+///
+/// # Examples
+/// ```
+/// use skirt::sync::TimedHold;
+/// use std::time::Duration;
+///
+/// let mutex = TimedHold::new(0, Duration::from_secs(1));
+///
+/// *mutex.lock() += 1;
+/// assert_eq!(*mutex.lock(), 1);
+/// ```
+///
+/// ```should_panic
+/// use skirt::sync::TimedHold;
+/// use std::time::Duration;
+///
+/// let mutex = TimedHold::new(0, Duration::from_millis(1));
+///
+/// let _guard = mutex.lock();
+/// std::thread::sleep(Duration::from_millis(10));
+/// ```
+pub struct TimedHold<T: ?Sized> {
+    max_hold: Duration,
+    mutex: Mutex<T>,
+}
+
+impl<T> TimedHold<T> {
+    /// Creates a new mutex whose guards may be held for at most `max_hold`.
+    #[inline]
+    pub const fn new(data: T, max_hold: Duration) -> Self {
+        Self {
+            max_hold,
+            mutex: Mutex::new(data),
+        }
+    }
+
+    /// Consumes this mutex, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.mutex.into_inner()
+    }
+}
+
+impl<T: ?Sized> TimedHold<T> {
+    /// Acquires the mutex, blocking the current thread until it is able to
+    /// do so.
+    ///
+    /// # Panics
+    /// The returned guard panics when dropped if it was held for longer than
+    /// [`max_hold`](Self::max_hold).
+    pub fn lock(&self) -> TimedHoldGuard<'_, T> {
+        TimedHoldGuard::new(self.mutex.lock(), self.max_hold)
+    }
+
+    /// Attempts to acquire the mutex without blocking.
+    #[must_use]
+    pub fn try_lock(&self) -> Option<TimedHoldGuard<'_, T>> {
+        self.mutex
+            .try_lock()
+            .map(|guard| TimedHoldGuard::new(guard, self.max_hold))
+    }
+
+    /// Returns the longest time a guard may be held.
+    #[inline]
+    #[must_use]
+    pub const fn max_hold(&self) -> Duration {
+        self.max_hold
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `TimedHold` mutably, no actual locking
+    /// needs to take place.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.mutex.get_mut()
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for TimedHold<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("TimedHold");
+
+        match self.mutex.try_lock() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+
+        d.field("max_hold", &self.max_hold).finish()
+    }
+}
+
+/// An RAII guard of a [`TimedHold`], which checks the hold time when dropped.
+///
+/// This structure is created by the [`lock`] and [`try_lock`] methods on
+/// [`TimedHold`].
+///
+/// [`lock`]: TimedHold::lock
+/// [`try_lock`]: TimedHold::try_lock
+#[cfg_attr(
+    feature = "nightly",
+    must_not_suspend = "other tasks spin on this mutex while it is held"
+)]
+pub struct TimedHoldGuard<'m, T: ?Sized> {
+    guard: MutexGuard<'m, T>,
+    acquired: Instant,
+    max_hold: Duration,
+}
+
+impl<'m, T: ?Sized> TimedHoldGuard<'m, T> {
+    fn new(guard: MutexGuard<'m, T>, max_hold: Duration) -> Self {
+        Self {
+            guard,
+            acquired: Instant::now(),
+            max_hold,
+        }
+    }
+
+    /// Returns how long the lock has been held by this guard.
+    #[must_use]
+    pub fn held_for(&self) -> Duration {
+        self.acquired.elapsed()
+    }
+}
+
+impl<T: ?Sized> Deref for TimedHoldGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<T: ?Sized> DerefMut for TimedHoldGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<T: ?Sized> Drop for TimedHoldGuard<'_, T> {
+    fn drop(&mut self) {
+        let held_for = self.held_for();
+
+        // The inner guard is still dropped, and the lock released, if this
+        // panics.
+        assert!(
+            held_for <= self.max_hold || std::thread::panicking(),
+            "lock held for {held_for:?}, longer than the bound of {:?}",
+            self.max_hold
+        );
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for TimedHoldGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for TimedHoldGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
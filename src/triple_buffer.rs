@@ -0,0 +1,177 @@
+use crate::sync::atomic::{AtomicU8, Ordering};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Formatter};
+
+const INDEX: u8 = 0b011;
+const DIRTY: u8 = 0b100;
+
+/// A triple buffer, letting a producer publish snapshots of a value to a
+/// consumer without either side ever blocking.
+///
+/// The producer writes into its own buffer and publishes it by atomically
+/// swapping it with the shared back buffer. The consumer swaps the back
+/// buffer with its own whenever a new snapshot has been published, and
+/// otherwise keeps reading the latest one it has. This suits real-time
+/// pipelines such as audio or sensor fusion where neither side may wait.
+///
+/// The two sides are obtained with [`split`](Self::split).
+///
+/// # Examples
+/// ```
+/// use skirt::sync::TripleBuffer;
+///
+/// let mut buffer = TripleBuffer::new(0);
+/// let (mut writer, mut reader) = buffer.split();
+///
+/// assert_eq!(*reader.read(), 0);
+///
+/// writer.write(1);
+/// writer.write(2);
+/// assert_eq!(*reader.read(), 2);
+///
+/// *writer.input() = 3;
+/// assert_eq!(*reader.read(), 2);
+/// writer.publish();
+/// assert_eq!(*reader.read(), 3);
+/// ```
+pub struct TripleBuffer<T> {
+    buffers: UnsafeCell<[T; 3]>,
+    back: AtomicU8,
+    write: u8,
+    read: u8,
+}
+
+impl<T> TripleBuffer<T> {
+    /// Creates a new triple buffer whose buffers all start out as `value`.
+    ///
+    /// This is not a `const fn`, since cloning `value` is not. Constants and
+    /// statics can use [`from_buffers`](Self::from_buffers) instead.
+    pub fn new(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_buffers([value.clone(), value.clone(), value])
+    }
+
+    /// Creates a new triple buffer from three initial buffers.
+    ///
+    /// The consumer initially reads the last buffer.
+    #[inline]
+    pub const fn from_buffers(buffers: [T; 3]) -> Self {
+        Self {
+            buffers: UnsafeCell::new(buffers),
+            back: AtomicU8::new(1),
+            write: 0,
+            read: 2,
+        }
+    }
+
+    /// Splits the buffer into its producer and consumer halves.
+    pub const fn split(&mut self) -> (TripleBufferWriter<'_, T>, TripleBufferReader<'_, T>) {
+        let Self {
+            buffers,
+            back,
+            write,
+            read,
+        } = self;
+
+        (
+            TripleBufferWriter {
+                buffers,
+                back,
+                write,
+            },
+            TripleBufferReader {
+                buffers,
+                back,
+                read,
+            },
+        )
+    }
+}
+
+impl<T: Default> Default for TripleBuffer<T> {
+    fn default() -> Self {
+        Self::from_buffers([T::default(), T::default(), T::default()])
+    }
+}
+
+impl<T> Debug for TripleBuffer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TripleBuffer").finish_non_exhaustive()
+    }
+}
+
+/// The writing half of a [`TripleBuffer`].
+pub struct TripleBufferWriter<'b, T> {
+    buffers: &'b UnsafeCell<[T; 3]>,
+    back: &'b AtomicU8,
+    write: &'b mut u8,
+}
+
+unsafe impl<T: Send> Send for TripleBufferWriter<'_, T> {}
+
+impl<T> TripleBufferWriter<'_, T> {
+    /// Returns the buffer being written, which is not visible to the
+    /// consumer until it is [published](Self::publish).
+    ///
+    /// The buffer holds whatever snapshot it last held, not necessarily the
+    /// most recently published one.
+    pub const fn input(&mut self) -> &mut T {
+        // SAFETY: The write buffer is owned by the writer.
+        unsafe { &mut *self.buffers.get().cast::<T>().add(*self.write as usize) }
+    }
+
+    /// Publishes the input buffer to the reader.
+    pub fn publish(&mut self) {
+        let back = self.back.swap(*self.write | DIRTY, Ordering::AcqRel);
+        *self.write = back & INDEX;
+    }
+
+    /// Writes `value` to the input buffer and publishes it.
+    pub fn write(&mut self, value: T) {
+        *self.input() = value;
+        self.publish();
+    }
+}
+
+impl<T> Debug for TripleBufferWriter<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TripleBufferWriter").finish_non_exhaustive()
+    }
+}
+
+/// The reading half of a [`TripleBuffer`].
+pub struct TripleBufferReader<'b, T> {
+    buffers: &'b UnsafeCell<[T; 3]>,
+    back: &'b AtomicU8,
+    read: &'b mut u8,
+}
+
+unsafe impl<T: Send> Send for TripleBufferReader<'_, T> {}
+
+impl<T> TripleBufferReader<'_, T> {
+    /// Returns whether a snapshot newer than the one last read has been
+    /// published.
+    #[must_use]
+    pub fn has_update(&self) -> bool {
+        self.back.load(Ordering::Relaxed) & DIRTY != 0
+    }
+
+    /// Returns the latest published snapshot.
+    pub fn read(&mut self) -> &T {
+        if self.has_update() {
+            let back = self.back.swap(*self.read, Ordering::AcqRel);
+            *self.read = back & INDEX;
+        }
+
+        // SAFETY: The read buffer is owned by the reader.
+        unsafe { &*self.buffers.get().cast::<T>().add(*self.read as usize) }
+    }
+}
+
+impl<T> Debug for TripleBufferReader<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TripleBufferReader").finish_non_exhaustive()
+    }
+}
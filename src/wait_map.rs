@@ -0,0 +1,167 @@
+use crate::mutex::Mutex;
+use crate::profile::Backoff;
+use core::fmt::{Debug, Formatter};
+
+/// A map of wait queues, letting threads block on arbitrary keys.
+///
+/// A thread calls [`wait`] with a key and blocks until another thread calls
+/// [`wake_one`] or [`wake_all`] with an equal key. This is the substrate for
+/// futex-like APIs on platforms without one, and for building custom
+/// primitives on top of plain atomics.
+///
+/// Up to `N` distinct keys can be waited on at once. A thread waiting on a
+/// new key while every slot is taken spins until one is released.
+///
+/// [`wait`]: Self::wait
+/// [`wake_one`]: Self::wake_one
+/// [`wake_all`]: Self::wake_all
+///
+/// # Examples
+/// ```
+/// use skirt::sync::WaitMap;
+///
+/// static WAITERS: WaitMap<usize> = WaitMap::new();
+///
+/// // Nobody is waiting, so there is nobody to wake.
+/// assert!(!WAITERS.wake_one(&7));
+///
+/// // A waiter whose condition no longer holds returns immediately.
+/// assert!(!WAITERS.wait_if(7, || false));
+/// ```
+pub struct WaitMap<K, const N: usize = 8> {
+    slots: Mutex<[Slot<K>; N]>,
+}
+
+struct Slot<K> {
+    key: Option<K>,
+    waiters: usize,
+    tokens: usize,
+}
+
+impl<K: Eq, const N: usize> WaitMap<K, N> {
+    /// Creates a new map with no waiters.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: Mutex::new(
+                [const {
+                    Slot {
+                        key: None,
+                        waiters: 0,
+                        tokens: 0,
+                    }
+                }; N],
+            ),
+        }
+    }
+
+    /// Blocks the current thread until it is woken through `key`.
+    pub fn wait(&self, key: K) {
+        self.wait_if(key, || true);
+    }
+
+    /// Blocks the current thread until it is woken through `key`, provided
+    /// `condition` returns `true`.
+    ///
+    /// `condition` is evaluated while the map is locked, so a waker that
+    /// changes the state it checks before calling [`wake_one`] or
+    /// [`wake_all`] can never be missed. It may be evaluated more than once
+    /// while every slot is taken. Returns whether the thread waited.
+    ///
+    /// [`wake_one`]: Self::wake_one
+    /// [`wake_all`]: Self::wake_all
+    pub fn wait_if<F>(&self, key: K, mut condition: F) -> bool
+    where
+        F: FnMut() -> bool,
+    {
+        let mut backoff = Backoff::new();
+
+        let index = loop {
+            let mut slots = self.slots.lock();
+
+            if !condition() {
+                return false;
+            }
+
+            let index = slots
+                .iter()
+                .position(|slot| slot.key.as_ref() == Some(&key))
+                .or_else(|| slots.iter().position(|slot| slot.key.is_none()));
+
+            if let Some(index) = index {
+                let slot = &mut slots[index];
+                slot.key.get_or_insert(key);
+                slot.waiters += 1;
+                break index;
+            }
+
+            drop(slots);
+            backoff.relax();
+        };
+
+        loop {
+            let mut slots = self.slots.lock();
+            let slot = &mut slots[index];
+
+            if slot.tokens > 0 {
+                slot.tokens -= 1;
+                slot.waiters -= 1;
+
+                if slot.waiters == 0 {
+                    slot.key = None;
+                }
+
+                return true;
+            }
+
+            drop(slots);
+            backoff.relax();
+        }
+    }
+
+    /// Wakes one thread waiting on `key`, returning whether there was one.
+    pub fn wake_one(&self, key: &K) -> bool {
+        self.wake(key, 1) == 1
+    }
+
+    /// Wakes every thread waiting on `key`, returning how many were woken.
+    pub fn wake_all(&self, key: &K) -> usize {
+        self.wake(key, usize::MAX)
+    }
+
+    fn wake(&self, key: &K, count: usize) -> usize {
+        let mut slots = self.slots.lock();
+
+        slots
+            .iter_mut()
+            .find(|slot| slot.key.as_ref() == Some(key))
+            .map_or(0, |slot| {
+                let woken = (slot.waiters - slot.tokens).min(count);
+                slot.tokens += woken;
+                woken
+            })
+    }
+}
+
+impl<K: Eq, const N: usize> Default for WaitMap<K, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, const N: usize> Debug for WaitMap<K, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("WaitMap");
+
+        match self.slots.try_lock() {
+            Some(slots) => d.field(
+                "waiters",
+                &slots.iter().map(|slot| slot.waiters).sum::<usize>(),
+            ),
+            None => d.field("waiters", &format_args!("<locked>")),
+        };
+
+        d.finish_non_exhaustive()
+    }
+}
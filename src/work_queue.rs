@@ -0,0 +1,171 @@
+use crate::isr_queue::{IsrQueue, OverflowPolicy};
+use crate::profile::Backoff;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use core::fmt::{Debug, Formatter};
+
+/// A bounded queue of jobs for a pool of workers, with backpressure and
+/// shutdown.
+///
+/// Producers [`push`] jobs, waiting while the queue is full, and workers
+/// [`pop`] them, waiting while it is empty. Once the queue is [`close`]d,
+/// pushing fails, and workers drain the remaining jobs before [`pop`]
+/// returns [`None`], which tells them to exit.
+///
+/// This is built on an [`IsrQueue`] that rejects jobs when full.
+///
+/// [`push`]: Self::push
+/// [`pop`]: Self::pop
+/// [`close`]: Self::close
+///
+/// # Examples
+/// ```
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+/// use skirt::sync::WorkQueue;
+///
+/// let queue = WorkQueue::<usize, 4>::new();
+/// let total = AtomicUsize::new(0);
+///
+/// std::thread::scope(|scope| {
+///     for _ in 0..3 {
+///         scope.spawn(|| {
+///             while let Some(job) = queue.pop() {
+///                 total.fetch_add(job, Ordering::Relaxed);
+///             }
+///         });
+///     }
+///
+///     for job in 1..=100 {
+///         queue.push(job).unwrap();
+///     }
+///
+///     queue.close();
+/// });
+///
+/// assert_eq!(total.load(Ordering::Relaxed), 5050);
+/// ```
+pub struct WorkQueue<T, const N: usize> {
+    jobs: IsrQueue<T, N>,
+    closed: AtomicBool,
+}
+
+impl<T, const N: usize> WorkQueue<T, N> {
+    /// Creates a new, empty and open queue.
+    ///
+    /// # Panics
+    /// Panics if `N` is not a power of two.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            jobs: IsrQueue::new(OverflowPolicy::Reject),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    /// Pushes a job, blocking while the queue is full.
+    ///
+    /// # Errors
+    /// Returns `job` back if the queue is closed, including while waiting.
+    pub fn push(&self, job: T) -> Result<(), T> {
+        let mut job = job;
+        let mut backoff = Backoff::new();
+
+        loop {
+            if self.is_closed() {
+                return Err(job);
+            }
+
+            match self.jobs.push_from_isr(job) {
+                Ok(()) => return Ok(()),
+                Err(rejected) => job = rejected,
+            }
+
+            backoff.relax();
+        }
+    }
+
+    /// Attempts to push a job without blocking.
+    ///
+    /// # Errors
+    /// Returns `job` back if the queue is closed or full.
+    pub fn try_push(&self, job: T) -> Result<(), T> {
+        if self.is_closed() {
+            return Err(job);
+        }
+
+        self.jobs.push_from_isr(job)
+    }
+
+    /// Takes the oldest job, blocking while the queue is empty.
+    ///
+    /// Returns [`None`] once the queue is closed and every job has been
+    /// taken.
+    pub fn pop(&self) -> Option<T> {
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Some(job) = self.jobs.pop() {
+                return Some(job);
+            }
+
+            if self.is_closed() {
+                // Take any job pushed just before the queue was closed.
+                return self.jobs.pop();
+            }
+
+            backoff.relax();
+        }
+    }
+
+    /// Attempts to take the oldest job without blocking.
+    pub fn try_pop(&self) -> Option<T> {
+        self.jobs.pop()
+    }
+
+    /// Closes the queue, making further pushes fail and letting workers
+    /// exit once the remaining jobs are drained.
+    ///
+    /// A push racing with this call may still succeed after the workers have
+    /// exited, in which case the job is dropped with the queue.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+    }
+
+    /// Returns whether the queue is closed.
+    #[inline]
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of queued jobs.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns whether no jobs are queued.
+    ///
+    /// This is only a snapshot and may be outdated by the time it is used.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+}
+
+impl<T, const N: usize> Default for WorkQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Debug for WorkQueue<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WorkQueue")
+            .field("len", &self.len())
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}